@@ -43,7 +43,7 @@ impl Solver<Year2020, Day1, Part2> for Solution {
     fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
         for (a_index, a) in input.iter().enumerate() {
             for (b_index, b) in input.iter().skip(a_index + 1).enumerate() {
-                for (_c_index, c) in input.iter().skip(b_index + 1).enumerate() {
+                for c in input.iter().skip(b_index + 1) {
                     if a + b + c == 2020 {
                         return Ok(a * b * c);
                     }
@@ -58,6 +58,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2020, Day1, Part1);
+aoc::register!(Solution, Year2020, Day1, Part2);
+
 #[test]
 fn test_first_solution() {
     let input = r#"
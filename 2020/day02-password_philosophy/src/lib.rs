@@ -102,6 +102,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2020, Day2, Part1);
+aoc::register!(Solution, Year2020, Day2, Part2);
+
 #[test]
 fn test_solution() {
     let input = r#"
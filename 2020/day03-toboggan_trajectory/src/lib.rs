@@ -84,6 +84,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2020, Day3, Part1);
+aoc::register!(Solution, Year2020, Day3, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
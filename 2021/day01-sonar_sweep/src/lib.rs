@@ -8,7 +8,7 @@
 //!
 //! *
 
-use aoc::{parts::*, Solver};
+use aoc::{parts::*, utils::IteratorExt, Solver};
 use eyre::Report;
 use itertools::Itertools;
 
@@ -41,7 +41,7 @@ impl Solver<Year2021, Day1, Part2> for Solution {
     }
 
     fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        let sums = input.iter().tuple_windows().map(|(a, b, c)| a + b + c);
+        let sums = input.iter().copied().sum_windows(3);
         Ok(sums.tuple_windows().filter(|(a, b)| b > a).count())
     }
 }
@@ -50,6 +50,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2021, Day1, Part1);
+aoc::register!(Solution, Year2021, Day1, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
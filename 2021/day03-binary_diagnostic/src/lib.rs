@@ -1,4 +1,3 @@
-#![feature(extract_if)]
 //! Advent of code 2021 3
 //!
 //!! Link: <https://adventofcode.com/2021/day/3>
@@ -9,59 +8,36 @@
 //!
 //! *
 
-use aoc::{parts::*, Solver};
+use aoc::{parts::*, utils::bitset, Solver};
 use eyre::Report;
 use itertools::Itertools;
 
 impl Solver<Year2021, Day3, Part1> for Solution {
     /// Input is each line, not parsed in column order
-    type Input<'a> = (usize, Vec<usize>);
+    type Input<'a> = (u32, Vec<u32>);
 
     type Output = usize;
 
     fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
         let len = input.lines().next().unwrap_or("").trim().len();
         Ok((
-            len,
+            len.try_into()?,
             input
                 .lines()
-                .map(|line| usize::from_str_radix(line.trim(), 2))
+                .map(|line| u32::from_str_radix(line.trim(), 2))
                 .try_collect()?,
         ))
     }
 
-    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        let mut gamma = 0usize;
-        let mut epsilon = 0usize;
-        // Length of samples
-        let sample_len = input.1.len();
-        for index in 0..input.0 {
-            // count zeroes in column
-            let count = input
-                .1
-                .iter()
-                .filter(|n| get_bit_at(**n as u32, index as u8))
-                .count();
-            gamma |= ((count > sample_len / 2) as usize) << index;
-            epsilon |= ((count <= sample_len / 2) as usize) << index;
-        }
-
-        Ok(gamma * epsilon)
-    }
-}
-
-/// Gets bit truthness at position
-#[inline]
-fn get_bit_at(input: u32, position: u8) -> bool {
-    if position < 32 {
-        input & (1 << position) != 0
-    } else {
-        false
+    fn solve((width, report): &Self::Input<'_>) -> Result<Self::Output, Report> {
+        let gamma = bitset::most_common_bits(report, *width);
+        let epsilon = bitset::least_common_bits(report, *width);
+        Ok(gamma as usize * epsilon as usize)
     }
 }
 
 impl Solver<Year2021, Day3, Part2> for Solution {
-    type Input<'a> = (usize, Vec<usize>);
+    type Input<'a> = <Self as Solver<Year2021, Day3, Part1>>::Input<'a>;
 
     type Output = usize;
 
@@ -69,61 +45,28 @@ impl Solver<Year2021, Day3, Part2> for Solution {
         <Self as Solver<Year2021, Day3, Part1>>::generate_input(input)
     }
 
-    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        let _oxygen = 0usize;
-        let _co2 = 0usize;
-        let mut oxygen_items = input.1.clone();
-        let mut co2_items = input.1.clone();
-
-        for index in (0..input.0).rev() {
-            // amount of zeroes
-
-            if oxygen_items.len() > 1 {
-                let count_ones = oxygen_items
-                    .iter()
-                    .filter(|n| get_bit_at(**n as u32, index as u8))
-                    .count();
-                let count_zeroes = oxygen_items.len() - count_ones;
-                tracing::info!(
-                    "got {count_ones} ones and {count_zeroes} zeroes. {} left",
-                    oxygen_items.len()
-                );
-
-                let remove = if count_ones != count_zeroes {
-                    count_ones <= oxygen_items.len() / 2
-                } else {
-                    false
-                };
-                tracing::info!("removing values that are {remove:?} for oxygen");
-                let _ = oxygen_items.extract_if(|n| get_bit_at(*n as u32, index as u8) == remove);
-                tracing::debug!(
-                    "{}",
-                    oxygen_items.iter().map(|n| format!("{n:b}")).join(" ")
-                );
-            }
-
-            if co2_items.len() > 1 {
-                let count_ones = co2_items
-                    .iter()
-                    .filter(|n| get_bit_at(**n as u32, index as u8))
-                    .count();
-                let count_zeroes = co2_items.len() - count_ones;
-                //tracing::info!("got {count_ones} ones and {count_zeroes} zeroes");
-                let remove = if count_ones != count_zeroes {
-                    count_ones > co2_items.len() / 2
-                } else {
-                    true
-                };
-                //tracing::info!("removing values that are {remove:?} for co2");
-                co2_items.extract_if(|n| get_bit_at(*n as u32, index as u8) == remove);
-            }
-        }
+    fn solve((width, report): &Self::Input<'_>) -> Result<Self::Output, Report> {
+        let oxygen = rating(report, *width, bitset::most_common_bit)?;
+        let co2 = rating(report, *width, bitset::least_common_bit)?;
+        Ok(oxygen as usize * co2 as usize)
+    }
+}
 
-        if co2_items.len() != 1 || oxygen_items.len() != 1 {
-            eyre::bail!("Too many items left");
+/// Keep the numbers whose bit matches `keep` of the remaining ones, column by
+/// column from the highest bit, until only one is left
+fn rating(report: &[u32], width: u32, keep: fn(&[u32], u32) -> bool) -> Result<u32, Report> {
+    let mut items = report.to_vec();
+    for position in (0..width).rev() {
+        if items.len() <= 1 {
+            break;
         }
-
-        Ok(co2_items[0] * oxygen_items[0])
+        let bit = keep(&items, position);
+        items.retain(|n| (n >> position & 1 == 1) == bit);
+        tracing::debug!("{}", items.iter().map(|n| format!("{n:b}")).join(" "));
+    }
+    match items[..] {
+        [rating] => Ok(rating),
+        _ => eyre::bail!("{} items left", items.len()),
     }
 }
 
@@ -131,6 +74,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2021, Day3, Part1);
+aoc::register!(Solution, Year2021, Day3, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
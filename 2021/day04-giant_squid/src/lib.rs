@@ -1,4 +1,3 @@
-#![feature(generic_associated_types)]
 //! Advent of code 2021 4
 //!
 //! https://adventofcode.com/2021/day/4
@@ -80,7 +79,7 @@ impl Board {
     }
     /// Determine if the board has a bingo
     pub fn bingo(&self) -> bool {
-        for mut chunk in &self
+        for chunk in &self
             .iter_marked()
             .chain(self.iter_marked_transpose())
             .chunks(WIDTH)
@@ -172,11 +171,10 @@ impl Solver<Year2021, Day4, Part1> for Solution {
         for board in &input.filter(|l| !l.trim().is_empty()).chunks(WIDTH) {
             let numbers = board
                 .inspect(|l| tracing::debug!("current line {l:?}"))
-                .map(|line| {
+                .flat_map(|line| {
                     line.split_ascii_whitespace()
                         .map(|n| n.parse().map_err::<eyre::Error, _>(Into::into))
                 })
-                .flatten()
                 .try_collect()?;
             boards.push(Board::new(numbers));
         }
@@ -234,6 +232,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2021, Day4, Part1);
+aoc::register!(Solution, Year2021, Day4, Part2);
+
 #[test]
 fn test_vertical() -> Result<(), Report> {
     aoc::test_util::init();
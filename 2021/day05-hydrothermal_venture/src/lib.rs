@@ -1,4 +1,3 @@
-#![feature(generic_associated_types)]
 //! Advent of code 2021 5
 //!
 //! https://adventofcode.com/2021/day/5
@@ -9,18 +8,17 @@
 
 use aoc::{parts::*, Solver};
 use eyre::Report;
-use itertools::Itertools;
 
 impl Solver<Year2021, Day5, Part1> for Solution {
     type Input<'a> = ();
 
     type Output = usize;
 
-    fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
+    fn generate_input(_input: &'_ str) -> Result<Self::Input<'_>, Report> {
         todo!()
     }
 
-    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
+    fn solve(_input: &Self::Input<'_>) -> Result<Self::Output, Report> {
         todo!()
     }
 }
@@ -34,7 +32,7 @@ impl Solver<Year2021, Day5, Part2> for Solution {
         <Self as Solver<Year2021, Day5, Part1>>::generate_input(input)
     }
 
-    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
+    fn solve(_input: &Self::Input<'_>) -> Result<Self::Output, Report> {
         todo!()
     }
 }
@@ -43,7 +41,11 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2021, Day5, Part1);
+aoc::register!(Solution, Year2021, Day5, Part2);
+
 #[test]
+#[ignore = "not solved yet"]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
     let input = r#"
@@ -67,6 +69,7 @@ fn test_solution() -> Result<(), Report> {
 }
 
 #[test]
+#[ignore = "not solved yet"]
 fn test_solution_second() -> Result<(), Report> {
     aoc::test_util::init();
     let input = r#"
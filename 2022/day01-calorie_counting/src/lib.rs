@@ -8,7 +8,7 @@
 //!
 //! *
 
-use aoc::{parts::*, Solver};
+use aoc::{parts::*, utils::IteratorExt, Solver};
 use eyre::{Context, Report};
 
 impl Solver<Year2022, Day1, Part1> for Solution {
@@ -50,11 +50,8 @@ impl Solver<Year2022, Day1, Part2> for Solution {
     }
 
     fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        let mut sums = input.iter().map(|v| v.iter().sum()).collect::<Vec<_>>();
-        sums.sort();
-        sums.reverse();
-
-        Ok(sums.iter().take(3).sum())
+        let top = input.iter().map(|v| v.iter().sum::<usize>()).top_k(3);
+        Ok(top.into_iter().sum())
     }
 }
 
@@ -62,6 +59,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2022, Day1, Part1);
+aoc::register!(Solution, Year2022, Day1, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -207,6 +207,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2022, Day2, Part1);
+aoc::register!(Solution, Year2022, Day2, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -8,7 +8,7 @@
 //!
 //! *
 
-use aoc::{parts::*, Solver};
+use aoc::{parts::*, utils::BitSet, Solver};
 use eyre::Report;
 use itertools::Itertools;
 
@@ -24,25 +24,9 @@ impl Solver<Year2022, Day3, Part1> for Solution {
     fn solve(input: &Vec<&'_ str>) -> Result<Self::Output, Report> {
         let mut total_priority = 0;
         for line in input {
-            let half = line.len() / 2;
-
-            let mut common_chars = std::collections::BTreeSet::new();
-            for ch in line[..half].chars() {
-                if line[half..].contains(ch) {
-                    common_chars.insert(ch);
-                }
-            }
-
-            let priority = common_chars
-                .iter()
-                .map(|ch: &char| match ch {
-                    'a'..='z' => ((*ch as u16) - 'a' as u16 + 1) as u32,
-                    'A'..='Z' => ((*ch as u16) - 'A' as u16 + 1) as u32 + 26,
-                    _ => panic!(),
-                })
-                .sum::<u32>();
-
-            total_priority += priority;
+            let (left, right) = line.split_at(line.len() / 2);
+            let common = BitSet::<1>::from_ascii_letters(left) & BitSet::from_ascii_letters(right);
+            total_priority += common.iter().sum::<usize>() as u32;
         }
         Ok(total_priority)
     }
@@ -60,26 +44,12 @@ impl Solver<Year2022, Day3, Part2> for Solution {
     fn solve(input: &Vec<&'_ str>) -> Result<Self::Output, Report> {
         let mut total_priority = 0;
         for line_3 in &input.iter().chunks(3) {
-            let mut common_chars = std::collections::BTreeMap::new();
-            for line in line_3 {
-                for ch in line.chars().unique() {
-                    *common_chars.entry(ch).or_insert(0) += 1;
-                }
-            }
-
-            let priority = match common_chars
-                .into_iter()
-                .find(|(_, i)| *i == 3)
-                .map(|(ch, i)| (ch, i))
-            {
-                Some((ch @ 'a'..='z', _)) => {
-                    Ok::<_, eyre::Report>(((ch as u16) - 'a' as u16 + 1) as u32)
-                }
-                Some((ch @ 'A'..='Z', _)) => Ok(((ch as u16) - 'A' as u16 + 1) as u32 + 26),
-                _ => eyre::bail!("wrong input"),
-            }?;
-
-            total_priority += priority;
+            let badge = line_3
+                .map(|line| BitSet::<1>::from_ascii_letters(line))
+                .reduce(|a, b| a & b)
+                .and_then(|common| common.first())
+                .ok_or_else(|| eyre::eyre!("wrong input"))?;
+            total_priority += badge as u32;
         }
         Ok(total_priority)
     }
@@ -89,6 +59,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2022, Day3, Part1);
+aoc::register!(Solution, Year2022, Day3, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -8,7 +8,7 @@
 //!
 //! *
 
-use aoc::{parts::*, Solver};
+use aoc::{parts::*, utils::IntervalSet, Solver};
 use eyre::Report;
 use itertools::Itertools;
 
@@ -39,11 +39,10 @@ impl Solver<Year2022, Day4, Part1> for Solution {
         let mut count = 0;
 
         for (a, b) in input {
-            let a = a.0..=a.1;
-            let b = b.0..=b.1;
-            if a.contains(b.start()) && a.contains(b.end())
-                || b.contains(a.start()) && b.contains(a.end())
-            {
+            let a = IntervalSet::from_range(a.0..=a.1);
+            let b = IntervalSet::from_range(b.0..=b.1);
+            let both = a.intersection(&b);
+            if both == a || both == b {
                 count += 1;
             }
         }
@@ -64,13 +63,7 @@ impl Solver<Year2022, Day4, Part2> for Solution {
         let mut count = 0;
 
         for (a, b) in input {
-            let a = a.0..=a.1;
-            let b = b.0..=b.1;
-            if a.contains(b.start())
-                || a.contains(b.end())
-                || b.contains(a.start())
-                || b.contains(a.end())
-            {
+            if IntervalSet::from_range(a.0..=a.1).overlaps(b.0..=b.1) {
                 count += 1;
             }
         }
@@ -82,6 +75,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2022, Day4, Part1);
+aoc::register!(Solution, Year2022, Day4, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
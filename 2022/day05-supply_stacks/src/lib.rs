@@ -10,7 +10,7 @@
 
 use std::str::FromStr;
 
-use aoc::{parts::*, utils::*, Solver};
+use aoc::{parts::*, utils::DisjointMut, Solver};
 use eyre::Report;
 use itertools::Itertools;
 use unicode_segmentation::UnicodeSegmentation;
@@ -57,10 +57,10 @@ impl<'a> Stacks<'a> {
     }
 
     pub fn find_top(&self, stack: usize) -> Option<StackIndex> {
-        return Some(StackIndex {
+        Some(StackIndex {
             position: self.arrays.get(stack)?.len() - 1,
             stack_num: stack,
-        });
+        })
     }
 
     pub fn get_top(&self, stack: usize) -> Option<&Krate<'a>> {
@@ -68,10 +68,7 @@ impl<'a> Stacks<'a> {
     }
 
     pub fn get(&self, idx: StackIndex) -> Option<&Krate<'a>> {
-        let Some(stack) = self.arrays.get(idx.stack_num) else {
-            return None;
-        };
-        stack.get(idx.position)
+        self.arrays.get(idx.stack_num)?.get(idx.position)
     }
 
     fn parse(stacks: &'a str) -> Result<Self, eyre::Report> {
@@ -289,6 +286,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2022, Day5, Part1);
+aoc::register!(Solution, Year2022, Day5, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
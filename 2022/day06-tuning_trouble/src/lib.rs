@@ -1,4 +1,3 @@
-#![feature(iter_array_chunks)]
 //! Advent of code 2022 6
 //!
 //!! Link: <https://adventofcode.com/2022/day/6>
@@ -9,9 +8,8 @@
 //!
 //! *
 
-use aoc::{parts::*, Solver};
+use aoc::{parts::*, utils::RollingWindows, Solver};
 use eyre::Report;
-use itertools::Itertools;
 
 impl Solver<Year2022, Day6, Part1> for Solution {
     type Input<'a> = &'a str;
@@ -23,12 +21,7 @@ impl Solver<Year2022, Day6, Part1> for Solution {
     }
 
     fn solve(input: &&'_ str) -> Result<Self::Output, Report> {
-        input
-            .chars()
-            .tuple_windows()
-            .position(|(a, b, c, d)| [a, b, c, d].iter().all_unique())
-            .ok_or_else(|| eyre::eyre!("none found"))
-            .map(|p| p + 4)
+        Solution::start_marker(input, 4)
     }
 }
 
@@ -42,17 +35,26 @@ impl Solver<Year2022, Day6, Part2> for Solution {
     }
 
     fn solve(input: &&str) -> Result<Self::Output, Report> {
-        let vec: Vec<_> = input.chars().collect();
-        vec.windows(14)
-            .position(|a| a.iter().all_unique())
-            .ok_or_else(|| eyre::eyre!("none found"))
-            .map(|p| p + 14)
+        Solution::start_marker(input, 14)
     }
 }
 
 pub struct Solution {}
 
-impl Solution {}
+impl Solution {
+    /// Characters read until the last `n` were all different
+    fn start_marker(input: &str, n: usize) -> Result<usize, Report> {
+        input
+            .as_bytes()
+            .rolling_distinct_counts(n)
+            .position(|count| count == n)
+            .ok_or_else(|| eyre::eyre!("none found"))
+            .map(|p| p + n)
+    }
+}
+
+aoc::register!(Solution, Year2022, Day6, Part1);
+aoc::register!(Solution, Year2022, Day6, Part2);
 
 #[test]
 fn test_solution() -> Result<(), Report> {
@@ -8,11 +8,10 @@
 //!
 //! *
 
-use std::{collections::HashMap, path::PathBuf, str::FromStr};
+use std::{collections::HashMap, path::PathBuf};
 
-use aoc::{parts::*, Solver};
-use eyre::{Context, Report};
-use itertools::Itertools;
+use aoc::{parts::*, DaySolver};
+use eyre::Report;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CommandOutput<'a> {
@@ -56,10 +55,15 @@ pub enum Entry<'a> {
     File(u32, &'a str),
 }
 
-impl Solver<Year2022, Day7, Part1> for Solution {
+impl DaySolver<Year2022, Day7> for Solution {
     type Input<'a> = Vec<CommandOutput<'a>>;
 
-    type Output = u64;
+    /// Total size of every directory
+    type State = HashMap<PathBuf, u64>;
+
+    type Output1 = u64;
+
+    type Output2 = u64;
 
     fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
         input
@@ -77,14 +81,14 @@ impl Solver<Year2022, Day7, Part1> for Solution {
             .collect::<Result<_, _>>()
     }
 
-    fn solve(input: &Vec<CommandOutput>) -> Result<Self::Output, Report> {
+    fn solve_first(input: &Vec<CommandOutput>) -> Result<(Self::Output1, Self::State), Report> {
         let mut dirs = HashMap::<PathBuf, u64>::new();
         let mut cwd = PathBuf::from("/");
         for command in input {
             match &command.command {
                 Command::Ls => {
                     for file in command.output.lines() {
-                        let Some((a, b)) = file.split_once(' ') else {
+                        let Some((a, _b)) = file.split_once(' ') else {
                             eyre::bail!("invalid ls output")
                         };
 
@@ -114,56 +118,14 @@ impl Solver<Year2022, Day7, Part1> for Solution {
                 },
             }
         }
-        Ok(dirs.values().filter(|&&size| size < 100000).copied().sum())
+        let small = dirs.values().filter(|&&size| size < 100000).copied().sum();
+        Ok((small, dirs))
     }
-}
-
-impl Solver<Year2022, Day7, Part2> for Solution {
-    type Input<'a> = <Self as Solver<2022, 7, Part1>>::Input<'a>;
-
-    type Output = <Self as Solver<2022, 7, Part1>>::Output;
-
-    fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
-        <Self as Solver<Year2022, Day7, Part1>>::generate_input(input)
-    }
-
-    fn solve(input: &Vec<CommandOutput>) -> Result<Self::Output, Report> {
-        let mut dirs = HashMap::<PathBuf, u64>::new();
-        let mut cwd = PathBuf::from("/");
-        for command in input {
-            match &command.command {
-                Command::Ls => {
-                    for file in command.output.lines() {
-                        let Some((a, b)) = file.split_once(' ') else {
-                            eyre::bail!("invalid ls output")
-                        };
 
-                        match a {
-                            "dir" => (),
-                            // match dirs.entry(cwd.join(b)) {
-                            // std::collections::hash_map::Entry::Occupied(_) => todo!(),
-                            // std::collections::hash_map::Entry::Vacant(v) => {
-                            //     v.insert(0);
-                            // }
-                            //  },
-                            size => {
-                                for dir in cwd.ancestors() {
-                                    *dirs.entry(dir.to_owned()).or_default() +=
-                                        size.parse::<u64>()?
-                                }
-                            }
-                        }
-                    }
-                }
-                Command::Cd(cd) => match cd {
-                    Cd::Back => {
-                        cwd.pop();
-                    }
-                    Cd::Root => cwd = PathBuf::from("/"),
-                    Cd::Dir(dir) => cwd = cwd.join(dir),
-                },
-            }
-        }
+    fn solve_second(
+        _input: &Vec<CommandOutput>,
+        dirs: Self::State,
+    ) -> Result<Self::Output2, Report> {
         let size = dirs
             .get(&PathBuf::from("/"))
             .ok_or_else(|| eyre::eyre!("no root??"))?;
@@ -183,6 +145,8 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register_day!(Solution, Year2022, Day7);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -150,6 +150,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2022, Day8, Part1);
+aoc::register!(Solution, Year2022, Day8, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -168,6 +171,7 @@ fn test_solution() -> Result<(), Report> {
 }
 
 #[test]
+#[ignore = "part 2 isn't solved yet"]
 fn test_solution_second() -> Result<(), Report> {
     aoc::test_util::init();
     let input = r#"
@@ -10,7 +10,6 @@
 
 use aoc::{parts::*, Solver};
 use eyre::Report;
-use itertools::Itertools;
 
 impl Solver<Year2023, Day1, Part1> for Solution {
     type Input<'a> = Vec<&'a str>;
@@ -47,7 +46,7 @@ impl Solver<Year2023, Day1, Part2> for Solution {
 
     fn solve(input: &Vec<&'_ str>) -> Result<Self::Output, Report> {
         let mut sum = 0;
-        const NUMBERS: &[(&'static str, usize)] = &[
+        const NUMBERS: &[(&str, usize)] = &[
             ("zero", 0),
             ("one", 1),
             ("two", 2),
@@ -99,7 +98,7 @@ impl Solver<Year2023, Day1, Part2> for Solution {
                 last_digit.unwrap().1 as usize
             };
             println!("last {last}");
-            sum += (first * 10) as usize + last;
+            sum += first * 10 + last;
         }
         Ok(sum)
     }
@@ -109,6 +108,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day1, Part1);
+aoc::register!(Solution, Year2023, Day1, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
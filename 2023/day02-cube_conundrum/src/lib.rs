@@ -10,7 +10,6 @@
 
 use aoc::{parts::*, Solver};
 use eyre::Report;
-use itertools::Itertools;
 
 pub struct Game {
     id: usize,
@@ -141,6 +140,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day2, Part1);
+aoc::register!(Solution, Year2023, Day2, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -26,7 +26,7 @@ struct Part<'a> {
 }
 #[derive(PartialEq, Eq)]
 
-struct Symbol<'a> {
+pub struct Symbol<'a> {
     kind: &'a str,
     idx: usize,
 }
@@ -56,40 +56,40 @@ impl Schematic {
     /// let board = "123..
     ///              *....";
     /// let schematic = Schematic::new(board);
-    /// assert!(schematic.touches_symbol(0..4), "under");
+    /// assert!(schematic.touches_symbol(0..4).is_some(), "under");
     ///
     /// let board = "123..
     ///              ...*.";
     /// let schematic = Schematic::new(board);
-    /// assert!(schematic.touches_symbol(0..4), "diag");
+    /// assert!(schematic.touches_symbol(0..4).is_some(), "diag");
     /// let board = "123..
     ///              ....*";
     /// let schematic = Schematic::new(board);
-    /// assert!(!schematic.touches_symbol(0..4), "not touching diag");
+    /// assert!(schematic.touches_symbol(0..4).is_none(), "not touching diag");
     /// let board = "123.*
     ///              .....";
     /// let schematic = Schematic::new(board);
-    /// assert!(!schematic.touches_symbol(0..4), "not touching right");
+    /// assert!(schematic.touches_symbol(0..4).is_none(), "not touching right");
     /// let board = "123*.
     ///              .....";
     /// let schematic = Schematic::new(board);
-    /// assert!(schematic.touches_symbol(0..4), "touching right");
+    /// assert!(schematic.touches_symbol(0..4).is_some(), "touching right");
     /// let board = "*23..
     ///              .....";
     /// let schematic = Schematic::new(board);
-    /// assert!(schematic.touches_symbol(1..4), "touching left");
+    /// assert!(schematic.touches_symbol(1..4).is_some(), "touching left");
     ///
     /// let board = "*.3.*
     ///              .....";
     /// let schematic = Schematic::new(board);
-    /// assert!(!schematic.touches_symbol(3..4), "not touching right or left");
+    /// assert!(schematic.touches_symbol(3..4).is_none(), "not touching right or left");
     /// let board = "*...*
     ///              *.1.*
     ///              *...*";
     /// let schematic = Schematic::new(board);
-    /// assert!(!schematic.touches_symbol(8..9), "not touching");
+    /// assert!(schematic.touches_symbol(8..9).is_none(), "not touching");
     /// ```
-    pub fn touches_symbol(&self, range: std::ops::Range<usize>) -> Option<Symbol> {
+    pub fn touches_symbol(&self, range: std::ops::Range<usize>) -> Option<Symbol<'_>> {
         let (s, e) = (range.start, range.end);
         let (left, right) = {
             // If adding or subtracting 1 would go over to the next line, skip
@@ -108,7 +108,7 @@ impl Schematic {
         let left_s = &self.board[(s + left).saturating_sub(2)..(s + left).saturating_sub(1)];
 
         if !left_s.is_empty() && !left_s.contains(|c: char| c == '.' || c.is_ascii_digit()) {
-            eprintln!("touching left: {}", &left_s);
+            eprintln!("touching left: {left_s}");
             return Some(Symbol {
                 kind: left_s,
                 idx: (s + left).saturating_sub(2),
@@ -223,7 +223,7 @@ impl Solver<Year2023, Day3, Part2> for Solution {
             if parts.len() > 2 {
                 continue;
             }
-            let Some(gear1) = parts.get(0) else { continue };
+            let Some(gear1) = parts.first() else { continue };
             let Some(gear2) = parts.get(1) else { continue };
             sum += gear1.number * gear2.number;
         }
@@ -235,6 +235,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day3, Part1);
+aoc::register!(Solution, Year2023, Day3, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -282,6 +285,7 @@ fn test_solution_second() -> Result<(), Report> {
 }
 
 #[test]
+#[ignore]
 fn print_input() {
     aoc::test_util::init();
     println!("{}", aoc::Aoc::new(&2023, &3).unwrap().input);
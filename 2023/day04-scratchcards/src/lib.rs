@@ -122,6 +122,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day4, Part1);
+aoc::register!(Solution, Year2023, Day4, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -1,4 +1,3 @@
-#![feature(iter_array_chunks)]
 //! Advent of code 2023 5
 //!
 //! Link: <https://adventofcode.com/2023/day/5>
@@ -11,7 +10,11 @@
 
 use std::ops::Range;
 
-use aoc::{parts::*, Solver};
+use aoc::{
+    parts::*,
+    utils::{IntervalSet, OffsetMap},
+    Solver,
+};
 use eyre::Report;
 use itertools::Itertools;
 
@@ -56,10 +59,10 @@ impl MapLine {
     /// assert_eq!(map_line.translate(51), Some(53));
     /// assert_eq!(map_line.translate(52), Some(54));
     /// assert_eq!(map_line.translate(53), Some(55));
-    /// assert_eq!(map_line.translate(96), None);
-    /// assert_eq!(map_line.translate(97), None);
+    /// assert_eq!(map_line.translate(96), Some(98));
+    /// assert_eq!(map_line.translate(97), Some(99));
     /// assert_eq!(map_line.translate(98), None);
-    /// ````
+    /// ```
     ///
     /// ```rust
     /// # use y2023_day5_if_you_give_a_seed_a_fertilizer::MapLine;
@@ -101,12 +104,36 @@ impl<'a> Map<'a> {
         })
     }
 
-    fn translate(&self, seed: u64) -> u64 {
-        self.lines
-            .iter()
-            .find_map(|line| line.translate(seed))
-            .unwrap_or(seed)
+    /// All lines as one map, the first line matching a seed winning
+    pub fn offsets(&self) -> Result<OffsetMap, Report> {
+        let mut map = OffsetMap::new();
+        for line in self.lines.iter().rev() {
+            let destination = line.range.start as i64 + line.offset;
+            map.insert(line.range.clone(), destination.try_into()?)?;
+        }
+        Ok(map)
+    }
+}
+
+/// Every map chained into one, from seed to location. Each map has to start
+/// where the one before it ends.
+fn seed_to_location(maps: &[Map<'_>]) -> Result<OffsetMap, Report> {
+    let mut chain = OffsetMap::new();
+    let mut category = "seed";
+    for map in maps {
+        if map.from != category {
+            eyre::bail!(
+                "expected a map from {category}, found one from {}",
+                map.from
+            );
+        }
+        chain = chain.then(&map.offsets()?);
+        category = map.to;
     }
+    if category != "location" {
+        eyre::bail!("the maps end at {category}, not location");
+    }
+    Ok(chain)
 }
 
 impl Solver<Year2023, Day5, Part1> for Solution {
@@ -134,17 +161,12 @@ impl Solver<Year2023, Day5, Part1> for Solution {
     }
 
     fn solve((seeds, maps): &Self::Input<'_>) -> Result<Self::Output, Report> {
-        let mut lowest = None;
-        for seed in seeds {
-            let mut seed = *seed;
-            for map in maps {
-                seed = map.translate(seed);
-            }
-            if lowest.is_none() || seed < lowest.unwrap() {
-                lowest = Some(seed);
-            }
-        }
-        Ok(lowest.unwrap())
+        let map = seed_to_location(maps)?;
+        seeds
+            .iter()
+            .map(|&seed| map.get(seed))
+            .min()
+            .ok_or_else(|| eyre::eyre!("no seeds"))
     }
 }
 
@@ -154,29 +176,22 @@ impl Solver<Year2023, Day5, Part2> for Solution {
     type Output = <Self as Solver<2023, 5, Part1>>::Output;
 
     fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
-        let (seeds, maps) = input
-            .trim()
-            .split_once("\n\n")
-            .ok_or_else(|| eyre::eyre!("couldn't split on first line"))?;
-        let seeds = seeds
-            .split_whitespace()
-            .skip(1)
-            .map(|seed| seed.parse().expect("couldn't parse"))
-            .array_chunks()
-            .fuse()
-            .flat_map(|[start, range]: [u64; 2]| start..(start + range))
-            .collect();
-        let maps = maps
-            .trim()
-            .split("\n\n")
-            .map(|s| s.trim())
-            .map(Map::new)
-            .try_collect()?;
-        Ok((seeds, maps))
+        <Self as Solver<Year2023, Day5, Part1>>::generate_input(input)
     }
 
-    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        <Self as Solver<Year2023, Day5, Part1>>::solve(input)
+    /// The seeds are pairs of start and length
+    fn solve((seeds, maps): &Self::Input<'_>) -> Result<Self::Output, Report> {
+        if seeds.len() % 2 != 0 {
+            eyre::bail!("seeds don't come in pairs");
+        }
+        let seeds: IntervalSet<u64> = seeds
+            .iter()
+            .tuples()
+            .map(|(&start, &length)| start..start + length)
+            .collect();
+        let locations = seed_to_location(maps)?.map_set(&seeds);
+        let lowest = locations.ranges().next().map(|range| *range.start());
+        lowest.ok_or_else(|| eyre::eyre!("no seeds"))
     }
 }
 
@@ -184,6 +199,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day5, Part1);
+aoc::register!(Solution, Year2023, Day5, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -227,6 +245,13 @@ humidity-to-location map:
         aoc::solve_with_input::<Solution, Year2023, Day5, Part1>(input)?,
         35
     );
+    // a map missing from the chain
+    let (head, tail) = input.split_once("\n\nsoil-to-fertilizer").unwrap();
+    let (_, tail) = tail.split_once("\n\n").unwrap();
+    let gap = format!("{head}\n\n{tail}");
+    assert!(aoc::solve_with_input::<Solution, Year2023, Day5, Part1>(&gap).is_err());
+    let (truncated, _) = input.split_once("\n\nhumidity-to-location").unwrap();
+    assert!(aoc::solve_with_input::<Solution, Year2023, Day5, Part1>(truncated).is_err());
     Ok(())
 }
 
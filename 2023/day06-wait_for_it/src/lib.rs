@@ -10,7 +10,6 @@
 
 use aoc::{parts::*, Solver};
 use eyre::Report;
-use itertools::Itertools;
 
 impl Solver<Year2023, Day6, Part1> for Solution {
     type Input<'a> = Vec<(u64, u64)>;
@@ -35,37 +34,12 @@ impl Solver<Year2023, Day6, Part1> for Solution {
     }
 
     fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        // find out the max time we can hold the button
-        // the distance the boat travels is the formula
-        // button_time * (allowed_time - button_time)
-        // since we want to find the first time we can beat the record
-        // we want to find the point
-        // button_time * (allowed_time - button_time) = record_distance
-        // if we expand the expression, we get
-        // -button_time^2 + allowed_time * button_time = record_distance
-        // which is a formula  like -x^2 + bx + c = 0
-        // so we can solve it with the quadratic formula
+        // holding the button for x ms beats the record when
+        // x * (time - x) > record, i.e. -x^2 + time * x - record > 0
         let mut prod = 1;
-        for (a, r) in input {
-            let a = *a as f64;
-            let r = *r as f64;
-            // let min_button_time = (-(*allowed_time as f64)
-            //     + ((allowed_time.pow(2) + 4 * record_distance) as f64).sqrt())
-            //     / 2.0;
-            //     let max_button_time = (-(*allowed_time as f64)
-            //     - ((allowed_time.pow(2) + 4 * record_distance) as f64).sqrt())
-            //     / 2.0;
-            let eps = 0.01;
-            let left = (a.powi(2) - 4.0 * r).sqrt() / 2.0;
-            let roots = [a / 2.0 - left + eps, a / 2.0 + left - eps];
-            // this simplifies (ignoreing ceil and floor), can we use that fact?
-            // floor(a/2 + left) - ceil(a/2 - left) + 1 =>
-            // left*2 + 1
-            // i'm not sure, the tricky part is that we need to offset by integer amounts
-            // also
-            let count = roots[1].trunc() as u64 - roots[0].ceil() as u64 + 1;
-            //println!("roots: {roots:?} - count: {count}",);
-            prod *= count;
+        for &(time, record) in input {
+            let wins = aoc::math::quadratic_positive(-1, time.try_into()?, -i64::try_from(record)?);
+            prod *= u64::try_from(wins.len())?;
         }
         Ok(prod)
     }
@@ -104,6 +78,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day6, Part1);
+aoc::register!(Solution, Year2023, Day6, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
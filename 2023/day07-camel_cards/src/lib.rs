@@ -8,9 +8,7 @@
 //!
 //! *
 
-use std::collections::BTreeMap;
-
-use aoc::{parts::*, Solver};
+use aoc::{parts::*, utils::IteratorExt, Solver};
 use eyre::Report;
 use itertools::Itertools;
 
@@ -63,62 +61,30 @@ pub enum Type {
 }
 
 impl Type {
-    fn _identify(map: BTreeMap<&Suit, i32>) -> Self {
-        let total = map.into_iter().collect_vec();
-        if total.len() == 1 {
-            Type::FiveOfAKind
-        } else if total.len() == 2 {
-            if total[0].1 == 4 || total[1].1 == 4 {
-                Type::FourOfAKind
-            } else {
-                Type::FullHouse
-            }
-        } else if total.len() == 3 {
-            if total.iter().any(|(_, ref c)| *c == 3) {
-                Type::ThreeOfAKind
-            } else {
-                Type::TwoPair
-            }
-        } else if total.len() == 4 {
-            Type::OnePair
-        } else {
-            Type::HighCard
+    /// Type of a hand from how many of each suit it has, by suit value
+    fn from_counts(counts: [usize; 15]) -> Self {
+        let top = counts.into_iter().top_k(2);
+        match top[..] {
+            [5, _] => Type::FiveOfAKind,
+            [4, _] => Type::FourOfAKind,
+            [3, 2] => Type::FullHouse,
+            [3, _] => Type::ThreeOfAKind,
+            [2, 2] => Type::TwoPair,
+            [2, _] => Type::OnePair,
+            _ => Type::HighCard,
         }
     }
 
     fn identify_p1(hand: &[Suit; 5]) -> Self {
-        let mut map = BTreeMap::new();
-
-        for suit in hand {
-            map.entry(suit).and_modify(|i| *i += 1).or_insert(1);
-        }
-        Type::_identify(map)
+        Type::from_counts(hand.iter().map(|&suit| suit as u8).counts_into_array())
     }
 
     fn identify_p2(hand: &[Suit; 5]) -> Self {
-        let mut map = BTreeMap::new();
-
-        for suit in hand {
-            map.entry(suit).and_modify(|i| *i += 1).or_insert(1);
-        }
-
-        let Some(jokers) = map.remove(&Suit::J) else {
-            return Type::_identify(map);
-        };
-        assert!(jokers >= 1);
-
-        //
-        match jokers {
-            j @ 1..=4 => {
-                *map.values_mut().max().unwrap() += j;
-            }
-            5 => {
-                map.insert(&Suit::J, 5);
-            }
-            _ => unreachable!(),
-        }
-
-        Type::_identify(map)
+        let mut counts: [usize; 15] = hand.iter().map(|&suit| suit as u8).counts_into_array();
+        // jokers join whatever there's most of
+        let jokers = std::mem::take(&mut counts[Suit::J as usize]);
+        *counts.iter_mut().max().expect("counts aren't empty") += jokers;
+        Type::from_counts(counts)
     }
 }
 
@@ -184,7 +150,7 @@ impl std::str::FromStr for Suit {
                 let i = b - b'0';
                 assert!(matches!(i, 2..=9));
                 // XXX: :)
-                unsafe { std::mem::transmute(i) }
+                unsafe { std::mem::transmute::<u8, Suit>(i) }
             }
             _ => unreachable!(),
         })
@@ -269,6 +235,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day7, Part1);
+aoc::register!(Solution, Year2023, Day7, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -8,13 +8,12 @@
 //!
 //! *
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use aoc::{parts::*, Solver};
+use aoc::{cycle::History, parts::*, Solver};
 use eyre::Report;
-use itertools::Itertools;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Waypoint<'a>(&'a str);
 
 impl<'a> std::fmt::Debug for Waypoint<'a> {
@@ -128,55 +127,49 @@ impl Solver<Year2023, Day8, Part2> for Solution {
     }
 
     fn solve(map: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        println!("{map:?}");
-        let mut current = map
-            .map
-            .keys()
-            .filter(|&wp| wp.0.ends_with('A'))
-            .collect_vec();
         let mut counts = Vec::new();
-        for current in current.iter_mut() {
-            let mut count = 0i64;
-            for instruction in map.instructions.iter().cycle() {
-                if current.0.ends_with('Z') {
-                    break;
-                }
-                let leftright = map
-                    .map
-                    .get(current)
-                    .ok_or_else(|| eyre::eyre!("couldn't get lr: current: {current:?}"))?;
-                *current = match &instruction {
-                    b'L' => &leftright.left,
-                    b'R' => &leftright.right,
-                    _ => unreachable!(),
-                };
-                count += 1;
-            }
-            counts.push(count);
+        for start in map.map.keys().filter(|wp| wp.0.ends_with('A')) {
+            counts.push(map.steps_to_z(start)?);
         }
-        Ok(counts.into_iter().reduce(lcm).unwrap() as usize)
+        let steps = aoc::math::lcm_all(counts).ok_or_else(|| eyre::eyre!("too many steps"))?;
+        Ok(steps as usize)
     }
 }
 
-fn lcm(first: i64, second: i64) -> i64 {
-    first * second / gcd(first, second)
-}
-
-fn gcd(first: i64, second: i64) -> i64 {
-    let mut max = first;
-    let mut min = second;
-    if min > max {
-        std::mem::swap(&mut max, &mut min);
+impl<'a> Map<'a> {
+    fn step<'m>(&'m self, (current, at): &(&'m Waypoint<'a>, usize)) -> (&'m Waypoint<'a>, usize) {
+        let Some(leftright) = self.map.get(*current) else {
+            // dead end, stay put so it shows up as a cycle
+            return (current, *at);
+        };
+        let next = match self.instructions[*at] {
+            b'L' => &leftright.left,
+            _ => &leftright.right,
+        };
+        (next, (at + 1) % self.instructions.len())
     }
 
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
+    /// Steps until a ghost starting at `start` first reaches a `Z` waypoint.
+    ///
+    /// Checks that it then reaches one at exactly every multiple of that
+    /// count, which is what makes the lcm of all ghosts the answer.
+    fn steps_to_z(&self, start: &Waypoint<'a>) -> Result<u64, Report> {
+        let history = History::new((start, 0), |state| self.step(state));
+        let cycle = history.cycle;
+        let is_z = |i: usize| history.states[i].0 .0.ends_with('Z');
+        let first = (0..history.states.len())
+            .find(|&i| is_z(i))
+            .ok_or_else(|| eyre::eyre!("{start:?} never reaches a Z"))?;
+        let expected: BTreeSet<usize> = (1..=cycle.period)
+            .map(|k| cycle.reduce(k * first))
+            .collect();
+        let reached: BTreeSet<usize> = (cycle.offset..history.states.len())
+            .filter(|&i| is_z(i))
+            .collect();
+        if first < cycle.offset || expected != reached {
+            eyre::bail!("{start:?} doesn't loop through Z every {first} steps, lcm won't work");
         }
-
-        max = min;
-        min = res;
+        Ok(first as u64)
     }
 }
 
@@ -184,6 +177,9 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day8, Part1);
+aoc::register!(Solution, Year2023, Day8, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -222,6 +218,19 @@ XXX = (XXX, XXX)
         aoc::solve_with_input::<Solution, Year2023, Day8, Part2>(input)?,
         6
     );
+
+    // reaches Z after 2, 5, 8, ... steps, which the lcm can't handle
+    let input = r#"
+L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11C, 11C)
+11C = (11D, 11D)
+11D = (11Z, 11Z)
+    "#
+    .trim();
+    assert!(aoc::solve_with_input::<Solution, Year2023, Day8, Part2>(input).is_err());
     Ok(())
 }
 
@@ -1,4 +1,3 @@
-#![feature(iter_map_windows)]
 //! Advent of code 2023 9
 //!
 //! Link: <https://adventofcode.com/2023/day/9>
@@ -9,9 +8,8 @@
 //!
 //! *
 
-use aoc::{parts::*, Solver};
+use aoc::{math::poly, parts::*, Solver};
 use eyre::Report;
-use itertools::Itertools;
 
 impl Solver<Year2023, Day9, Part1> for Solution {
     type Input<'a> = Vec<Vec<i64>>;
@@ -31,31 +29,7 @@ impl Solver<Year2023, Day9, Part1> for Solution {
     }
 
     fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        let mut sum = 0;
-        for oasis_line in input {
-            let mut lines = vec![oasis_line.clone()];
-            loop {
-                lines.push(
-                    lines
-                        .last()
-                        .unwrap()
-                        .iter()
-                        .map_windows(|[a, b]| *b - *a)
-                        .collect_vec(),
-                );
-                if lines.last().unwrap().iter().all(|a| a == &0) {
-                    println!("{:?}", lines);
-                    break;
-                }
-            }
-            let mut solutions = vec![0];
-            for next in lines.iter().rev().skip(1) {
-                solutions.push(next.last().unwrap() + solutions.last().unwrap());
-                println!("next: {next:?}, sols: {solutions:?}");
-            }
-            sum += *solutions.last().unwrap();
-        }
-        Ok(sum)
+        sum_extrapolated(input, poly::next)
     }
 }
 
@@ -69,38 +43,29 @@ impl Solver<Year2023, Day9, Part2> for Solution {
     }
 
     fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
-        let mut sum = 0;
-        for oasis_line in input {
-            let mut lines = vec![oasis_line.clone()];
-            loop {
-                lines.push(
-                    lines
-                        .last()
-                        .unwrap()
-                        .iter()
-                        .map_windows(|[a, b]| *b - *a)
-                        .collect_vec(),
-                );
-                if lines.last().unwrap().iter().all(|a| a == &0) {
-                    println!("{:?}", lines);
-                    break;
-                }
-            }
-            let mut solutions = vec![0];
-            for next in lines.iter().rev().skip(1) {
-                solutions.push(next.first().unwrap() - solutions.last().unwrap());
-                println!("next: {next:?}, sols: {solutions:?}");
-            }
-            sum += *solutions.last().unwrap();
-        }
-        Ok(sum)
+        sum_extrapolated(input, poly::previous)
     }
 }
 
+/// Sum of `extrapolate` over every history
+fn sum_extrapolated(
+    input: &[Vec<i64>],
+    extrapolate: fn(&[i64]) -> Option<i128>,
+) -> Result<i64, Report> {
+    let mut sum = 0i128;
+    for history in input {
+        sum += extrapolate(history).ok_or_else(|| eyre::eyre!("can't extrapolate {history:?}"))?;
+    }
+    Ok(sum.try_into()?)
+}
+
 pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year2023, Day9, Part1);
+aoc::register!(Solution, Year2023, Day9, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
@@ -130,6 +95,15 @@ fn test_solution_second() -> Result<(), Report> {
         aoc::solve_with_input::<Solution, Year2023, Day9, Part2>(input)?,
         2
     );
+    // the differences only settle on the last single value
+    assert_eq!(
+        aoc::solve_with_input::<Solution, Year2023, Day9, Part1>("1 2 4\n3")?,
+        10
+    );
+    assert_eq!(
+        aoc::solve_with_input::<Solution, Year2023, Day9, Part2>("1 2 4\n3")?,
+        4
+    );
     Ok(())
 }
 
@@ -0,0 +1,99 @@
+//! Check every registered solver against the accepted answers in the cache
+
+use std::{fmt, path::Path};
+
+use aoc::{registry::SolverEntry, Aoc, Level};
+use eyre::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+
+#[derive(Debug)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        got: String,
+    },
+    Error(eyre::Report),
+    /// No input or accepted answer to check against
+    Missing(&'static str),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "{}", "pass".green()),
+            Status::Fail { expected, got } => {
+                write!(f, "{} expected {expected}, got {got}", "fail".red())
+            }
+            Status::Error(e) => write!(f, "{} {e:#}", "error".red()),
+            Status::Missing(what) => write!(f, "{} {what}", "missing".yellow()),
+        }
+    }
+}
+
+/// Run `solver` on its cached input and compare with the accepted answer
+pub fn check(aoc: Option<&Aoc>, solver: &SolverEntry) -> Status {
+    let Some(aoc) = aoc else {
+        return Status::Missing("no cached input");
+    };
+    let Some(expected) = Level::from_part(solver.part).and_then(|l| aoc.solution.get(&l)) else {
+        return Status::Missing("no accepted answer");
+    };
+    match solver.solve(&aoc.input) {
+        Ok(got) if got == *expected => Status::Pass,
+        Ok(got) => Status::Fail {
+            expected: expected.clone(),
+            got,
+        },
+        Err(e) => Status::Error(e),
+    }
+}
+
+/// Check all `solvers`, print a table of the results and fail if any solver
+/// didn't produce the accepted answer.
+pub fn run(root: &Path, solvers: Vec<&'static SolverEntry>) -> Result<()> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    println!("{:<4} {:>3} {:>4}  status", "year", "day", "part");
+    for ((year, day), solvers) in &solvers.into_iter().group_by(|s| (s.year, s.day)) {
+        let aoc = Aoc::on_root_dir(root, &year, &day).ok();
+        for solver in solvers {
+            let status = check(aoc.as_ref(), solver);
+            match status {
+                Status::Pass => passed += 1,
+                Status::Fail { .. } | Status::Error(_) => failed += 1,
+                Status::Missing(_) => missing += 1,
+            }
+            println!("{year:<4} {day:>3} {:>4}  {status}", solver.part);
+        }
+    }
+    println!("\n{passed} passed, {failed} failed, {missing} missing");
+
+    if failed > 0 {
+        eyre::bail!("{failed} solver(s) didn't produce the accepted answer");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_check() {
+    let solver = aoc::registry::get(2023, 8, 1).unwrap();
+    let mut aoc = Aoc {
+        year: 2023,
+        day: 8,
+        level: Level::Second,
+        title: "Haunted Wasteland".to_owned(),
+        stars: Some(1),
+        solution: Default::default(),
+        input: "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)".to_owned(),
+        brief: Default::default(),
+    };
+    assert!(matches!(check(None, solver), Status::Missing(_)));
+    assert!(matches!(check(Some(&aoc), solver), Status::Missing(_)));
+    aoc.solution.insert(Level::First, "6".to_owned());
+    assert!(matches!(check(Some(&aoc), solver), Status::Pass));
+    aoc.solution.insert(Level::First, "7".to_owned());
+    assert!(matches!(check(Some(&aoc), solver), Status::Fail { .. }));
+    aoc.input = "LLR".to_owned();
+    assert!(matches!(check(Some(&aoc), solver), Status::Error(_)));
+}
@@ -0,0 +1,29 @@
+//! Links every day crate into the runner.
+//!
+//! Generated by `cargo xtask new-day`, do not edit.
+
+use y2020_day1_report_repair as _;
+use y2020_day2_password_philosophy as _;
+use y2020_day3_toboggan_trajectory as _;
+use y2021_day1_sonar_sweep as _;
+use y2021_day2_dive as _;
+use y2021_day3_binary_diagnostic as _;
+use y2021_day4_giant_squid as _;
+use y2021_day5_hydrothermal_venture as _;
+use y2022_day1_calorie_counting as _;
+use y2022_day2_rock_paper_scissors as _;
+use y2022_day3_rucksack_reorganization as _;
+use y2022_day4_camp_cleanup as _;
+use y2022_day5_supply_stacks as _;
+use y2022_day6_tuning_trouble as _;
+use y2022_day7_no_space_left_on_device as _;
+use y2022_day8_treetop_tree_house as _;
+use y2023_day1_trebuchet as _;
+use y2023_day2_cube_conundrum as _;
+use y2023_day3_gear_ratios as _;
+use y2023_day4_scratchcards as _;
+use y2023_day5_if_you_give_a_seed_a_fertilizer as _;
+use y2023_day6_wait_for_it as _;
+use y2023_day7_camel_cards as _;
+use y2023_day8_haunted_wasteland as _;
+use y2023_day9_mirage_maintenance as _;
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+xflags::xflags! {
+    src "./src/flags.rs"
+    cmd aoc
+    {
+        /// Run the solvers for a year, a day or a single part.
+        cmd run {
+            optional year: u32
+            optional day: u32
+            /// Only run this part.
+            optional -p, --part part: u32
+            /// Run every registered solver.
+            optional -a, --all
+            /// Read the input from this file instead of the cache, `-` reads stdin.
+            optional -i, --input input: PathBuf
+            /// Print the answers and timings as json.
+            optional --json
+        }
+        /// Check the solvers against the accepted answers in the cache.
+        cmd check {
+            optional year: u32
+            optional day: u32
+        }
+    }
+}
+// generated start
+// The following code is generated by `xflags` macro.
+// Run `env UPDATE_XFLAGS=1 cargo build` to regenerate.
+#[derive(Debug)]
+pub struct Aoc {
+    pub subcommand: AocCmd,
+}
+
+#[derive(Debug)]
+pub enum AocCmd {
+    Run(Run),
+    Check(Check),
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+
+    pub part: Option<u32>,
+    pub all: bool,
+    pub input: Option<PathBuf>,
+    pub json: bool,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+}
+
+impl Aoc {
+    #[allow(dead_code)]
+    pub fn from_env_or_exit() -> Self {
+        Self::from_env_or_exit_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_env() -> xflags::Result<Self> {
+        Self::from_env_()
+    }
+
+    #[allow(dead_code)]
+    pub fn from_vec(args: Vec<std::ffi::OsString>) -> xflags::Result<Self> {
+        Self::from_vec_(args)
+    }
+}
+// generated end
@@ -0,0 +1,206 @@
+use std::{io::Read, time::Duration};
+
+use aoc::{
+    alloc::Allocations,
+    registry::{Run, SolverEntry},
+    Timings,
+};
+use eyre::{Result, WrapErr};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+mod check;
+mod days;
+mod flags;
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let flags = flags::Aoc::from_env_or_exit();
+
+    match flags.subcommand {
+        flags::AocCmd::Run(run) => {
+            self::run(&run)?;
+        }
+        flags::AocCmd::Check(flags) => {
+            let solvers = select(flags.year, flags.day, None);
+            check::run(&aoc::aoc::find_root()?, solvers)?;
+        }
+    };
+
+    Ok(())
+}
+
+fn run(flags: &flags::Run) -> Result<()> {
+    if flags.all && (flags.year.is_some() || flags.day.is_some()) {
+        eyre::bail!("`--all` can't be combined with a year or day");
+    }
+    if !flags.all && flags.year.is_none() {
+        eyre::bail!("expected a year or `--all`");
+    }
+    let solvers = select(flags.year, flags.day, flags.part);
+    if solvers.is_empty() {
+        eyre::bail!("no solvers found");
+    }
+
+    let input = match &flags.input {
+        Some(path) if path.as_os_str() == "-" => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Some(input)
+        }
+        Some(path) => Some(
+            std::fs::read_to_string(path)
+                .wrap_err_with(|| format!("could not read input {}", path.display()))?,
+        ),
+        None => None,
+    };
+    if input.is_some()
+        && solvers
+            .iter()
+            .any(|s| (s.year, s.day) != (solvers[0].year, solvers[0].day))
+    {
+        eyre::bail!("`--input` can only be used when running a single day");
+    }
+
+    let root = if input.is_none() {
+        Some(aoc::aoc::find_root()?)
+    } else {
+        None
+    };
+
+    let mut records = vec![];
+    if !flags.json {
+        println!(
+            "{:<4} {:>3} {:>4} {:>10} {:>10} {:>10}  answer",
+            "year", "day", "part", "parse", "solve", "total"
+        );
+    }
+    for ((year, day), solvers) in &solvers.into_iter().group_by(|s| (s.year, s.day)) {
+        let day_input = match (&input, &root) {
+            (Some(input), _) => Ok(input.clone()),
+            (None, Some(root)) => aoc::Aoc::on_root_dir(root, &year, &day).map(|aoc| aoc.input),
+            (None, None) => unreachable!(),
+        };
+
+        let solvers: Vec<_> = solvers.collect();
+        // parse only once if the day can solve both parts together
+        let mut results = match (aoc::registry::get_day(year, day), &day_input) {
+            (Some(entry), Ok(input)) if solvers.len() == 2 => entry.run(input).map(Some),
+            _ => [None, None],
+        }
+        .into_iter();
+        for solver in solvers {
+            let result = results.next().flatten().unwrap_or_else(|| {
+                day_input
+                    .as_ref()
+                    .map_err(|e| eyre::eyre!("{e:?}"))
+                    .and_then(|input| solver.run(input))
+            });
+            let record = Record::new(solver, result);
+            if !flags.json {
+                record.print();
+            }
+            records.push(record);
+        }
+    }
+    if flags.json {
+        println!("{}", serde_json::to_string_pretty(&records)?);
+    }
+
+    let failed = records.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        eyre::bail!("{failed} solver(s) failed");
+    }
+    Ok(())
+}
+
+/// The result of running a single solver
+#[derive(Debug, Serialize)]
+struct Record {
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: Option<String>,
+    error: Option<String>,
+    #[serde(flatten)]
+    timings: Option<Nanos>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocations: Option<Allocations>,
+}
+
+/// [`Timings`] in nanoseconds
+#[derive(Debug, Serialize)]
+struct Nanos {
+    parse_ns: u128,
+    solve_ns: u128,
+    total_ns: u128,
+}
+
+impl Record {
+    fn new(solver: &SolverEntry, result: Result<Run>) -> Self {
+        let (answer, error, timings, allocations) = match result {
+            Ok(Run {
+                answer,
+                timings,
+                allocations,
+            }) => (Some(answer), None, Some(timings), allocations),
+            Err(e) => (None, Some(format!("{e:?}")), None, None),
+        };
+        Self {
+            year: solver.year,
+            day: solver.day,
+            part: solver.part,
+            answer,
+            error,
+            timings: timings.map(|t: Timings| Nanos {
+                parse_ns: t.parse.as_nanos(),
+                solve_ns: t.solve.as_nanos(),
+                total_ns: t.total().as_nanos(),
+            }),
+            allocations,
+        }
+    }
+
+    fn print(&self) {
+        let (year, day, part) = (self.year, self.day, self.part);
+        match (&self.answer, &self.timings, &self.error) {
+            (Some(answer), Some(t), _) => {
+                let fmt = |ns: u128| format!("{:.2?}", Duration::from_nanos(ns as u64));
+                println!(
+                    "{year:<4} {day:>3} {part:>4} {:>10} {:>10} {:>10}  {}",
+                    fmt(t.parse_ns).dimmed(),
+                    fmt(t.solve_ns).dimmed(),
+                    fmt(t.total_ns),
+                    answer.green(),
+                );
+                if let Some(Allocations { parse, solve }) = &self.allocations {
+                    let fmt = |a: &aoc::alloc::AllocStats| {
+                        format!("{} allocs, {} B, peak {} B", a.allocations, a.bytes, a.peak)
+                    };
+                    println!(
+                        "{:>14} parse: {}, solve: {}",
+                        "",
+                        fmt(parse).dimmed(),
+                        fmt(solve).dimmed()
+                    );
+                }
+            }
+            (_, _, error) => println!(
+                "{year:<4} {day:>3} {part:>4} {:>32}  {}",
+                "failed".red(),
+                error.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Select the registered solvers matching the given year, day and part.
+fn select(year: Option<u32>, day: Option<u32>, part: Option<u32>) -> Vec<&'static SolverEntry> {
+    aoc::registry::solvers()
+        .into_iter()
+        .filter(|s| year.is_none_or(|year| s.year == year))
+        .filter(|s| day.is_none_or(|day| s.day == day))
+        .filter(|s| part.is_none_or(|part| s.part == part))
+        .collect()
+}
@@ -0,0 +1,151 @@
+//! Heap allocation accounting.
+//!
+//! [`CountingAllocator`] wraps another allocator and counts every allocation
+//! it makes. It's installed as the global allocator with the
+//! `count-allocations` feature, e.g.
+//!
+//! ```sh
+//! cargo run --release -p runner --features count-allocations -- run 2022 8
+//! cargo bench -p y2022_day8-treetop_tree_house --features aoc/count-allocations
+//! ```
+//!
+//! Without it, [`measure`] returns no statistics.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde::Serialize;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator(System);
+
+/// An allocator that counts allocations made through `A`
+pub struct CountingAllocator<A = System>(pub A);
+
+impl<A> CountingAllocator<A> {
+    fn record_alloc(&self, size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(&self, size: usize) {
+        LIVE.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: All allocation is forwarded to `A`, we only count on the side.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout);
+        self.record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.0.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.record_dealloc(layout.size());
+            self.record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made while running some code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations count as one
+    pub allocations: usize,
+    /// Total bytes allocated
+    pub bytes: usize,
+    /// Most bytes live at the same time, on top of what was live before
+    pub peak: usize,
+}
+
+/// Allocations made while parsing and solving
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Allocations {
+    pub parse: AllocStats,
+    pub solve: AllocStats,
+}
+
+/// Whether the [`CountingAllocator`] is the global allocator, which it is with
+/// the `count-allocations` feature
+pub fn is_installed() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// Run `f` and count the allocations it makes.
+///
+/// Returns `None` for the stats if the [`CountingAllocator`] isn't installed.
+/// Allocations on other threads are counted too.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !is_installed() {
+        return (f(), None);
+    }
+    let (ret, stats) = count(f);
+    (ret, Some(stats))
+}
+
+/// What the counters went up by while running `f`
+fn count<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated = ALLOCATED.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+
+    let ret = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED.load(Ordering::Relaxed) - allocated,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (ret, stats)
+}
+
+#[test]
+fn test_counting_allocator() {
+    let allocator = CountingAllocator(System);
+    let layout = Layout::from_size_align(1024, 8).unwrap();
+    let (_, stats) = count(|| unsafe {
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc_zeroed(layout);
+        allocator.dealloc(a, layout);
+        let b = allocator.realloc(b, layout, 4096);
+        allocator.dealloc(b, Layout::from_size_align(4096, 8).unwrap());
+    });
+    // other tests may allocate at the same time when the counting allocator
+    // is installed globally
+    assert!(stats.allocations >= 3, "{stats:?}");
+    assert!(stats.bytes >= 1024 + 1024 + 4096, "{stats:?}");
+    assert!(stats.peak >= 4096, "{stats:?}");
+
+    let (_, measured) = measure(Vec::<u8>::new);
+    assert_eq!(measured.is_some(), cfg!(feature = "count-allocations"));
+}
@@ -1,9 +1,14 @@
-use std::{collections::BTreeMap, fmt, path::Path};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
 use eyre::WrapErr;
 use serde::{Deserialize, Serialize};
 
-use crate::Solver;
+use crate::{Solver, Timings};
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -12,6 +17,24 @@ pub enum Level {
     Second,
 }
 
+impl Level {
+    /// The part number of this level, `1` or `2`
+    pub fn part(self) -> u32 {
+        match self {
+            Level::First => 1,
+            Level::Second => 2,
+        }
+    }
+
+    pub fn from_part(part: u32) -> Option<Self> {
+        match part {
+            1 => Some(Level::First),
+            2 => Some(Level::Second),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for Level {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.serialize(f)
@@ -41,19 +64,49 @@ impl Aoc {
         year: &impl fmt::Display,
         day: &impl fmt::Display,
     ) -> eyre::Result<Self> {
-        let file_path = root.join(format!(".aocf/cache/aoc{year}_{day:0>2}.json"));
+        let file_path = Self::cache_path(root, year, day);
         let file = std::fs::File::open(&file_path)
             .wrap_err(format!("could not open aoc file on {file_path:?}"))?;
-        serde_json::from_reader(&file)
-            .wrap_err("when trying to deserialize aoc")
-            .map_err(Into::into)
+        serde_json::from_reader(&file).wrap_err("when trying to deserialize aoc")
+    }
+
+    /// Write this puzzle to the cache in `root`, where [`Aoc::on_root_dir`]
+    /// reads it from.
+    pub fn save_on_root_dir(&self, root: &Path) -> eyre::Result<()> {
+        let file_path = Self::cache_path(root, &self.year, &self.day);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)
+                .wrap_err(format!("could not create cache dir {parent:?}"))?;
+        }
+        let file = std::fs::File::create(&file_path)
+            .wrap_err(format!("could not create aoc file on {file_path:?}"))?;
+        serde_json::to_writer_pretty(&file, self).wrap_err("when trying to serialize aoc")
+    }
+
+    /// Path to the cached puzzle for `year` and `day`
+    pub fn cache_path(root: &Path, year: &impl fmt::Display, day: &impl fmt::Display) -> PathBuf {
+        root.join(format!(".aocf/cache/aoc{year}_{day:0>2}.json"))
     }
 
     pub fn solve<S: Solver<YEAR, DAY, PART>, const YEAR: u32, const DAY: u32, const PART: u32>(
     ) -> eyre::Result<S::Output> {
         let aoc = Self::new(&YEAR, &DAY)?;
+        let start = Instant::now();
         let input = S::generate_input(&aoc.input).wrap_err("could not create input")?;
-        S::solve(&input).wrap_err("could not solve {YEAR}-{DAY}-{PART}")
+        let parse = start.elapsed();
+        let start = Instant::now();
+        let output = S::solve(&input).wrap_err("could not solve {YEAR}-{DAY}-{PART}")?;
+        let timings = Timings {
+            parse,
+            solve: start.elapsed(),
+        };
+        tracing::info!(
+            parse = ?timings.parse,
+            solve = ?timings.solve,
+            total = ?timings.total(),
+            "solved {YEAR}-{DAY}-{PART}"
+        );
+        Ok(output)
     }
 }
 
@@ -61,13 +114,10 @@ impl Aoc {
 pub fn find_root() -> eyre::Result<std::path::PathBuf> {
     let cwd = std::env::current_dir()?;
 
-    let conf_dir = cwd
-        .ancestors()
-        .find(|dir| dir.join(".aocf").is_dir())
-        .filter(|dir| dir.join(".aocf/config").is_file());
+    let conf_dir = cwd.ancestors().find(|dir| dir.join(".aocf").is_dir());
 
     match conf_dir {
         Some(dir) => Ok(dir.to_path_buf()),
-        None => eyre::bail!("no configuration found, maybe you need to run `aocf init`"),
+        None => eyre::bail!("no configuration found, maybe you need to run `cargo xtask fetch`"),
     }
 }
@@ -0,0 +1,33 @@
+//! Criterion benchmarks shared by every day's `benches/solution_crit.rs`,
+//! with the `bench` feature.
+
+use criterion::{black_box, Criterion};
+
+use crate::Solver;
+
+/// Benchmark parsing, solving and both together for a part.
+///
+/// Allocations of one parse and solve are printed too when the counting
+/// allocator is installed, see [`alloc`](crate::alloc).
+pub fn bench_part<S, const YEAR: u32, const DAY: u32, const PART: u32>(
+    c: &mut Criterion,
+    input: &str,
+) where
+    S: Solver<YEAR, DAY, PART>, {
+    let mut group = c.benchmark_group(format!("solve {YEAR} day {DAY} part{PART}"));
+    group.bench_function("parse", |b| {
+        b.iter_with_large_drop(|| S::generate_input(black_box(input)).unwrap())
+    });
+    let (parsed, parse_allocations) = crate::alloc::measure(|| S::generate_input(input).unwrap());
+    let (_, solve_allocations) = crate::alloc::measure(|| S::solve(&parsed).unwrap());
+    if let (Some(parse), Some(solve)) = (parse_allocations, solve_allocations) {
+        println!("{YEAR} day {DAY} part{PART} allocations, parse: {parse:?}, solve: {solve:?}");
+    }
+    group.bench_function("solve", |b| {
+        b.iter(|| S::solve(black_box(&parsed)).unwrap())
+    });
+    group.bench_function("total", |b| {
+        b.iter(|| crate::solve_with_input::<S, YEAR, DAY, PART>(black_box(input)).unwrap())
+    });
+    group.finish();
+}
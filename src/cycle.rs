@@ -0,0 +1,178 @@
+//! Finding where an iterated simulation starts repeating.
+//!
+//! All functions take a starting state and a `step` function producing the
+//! next state, and only terminate if the sequence eventually repeats.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use aoc::cycle::{brent, nth_state, Cycle};
+//!
+//! // 3, 9, 27 % 10 = 7, 21 % 10 = 1, 3, ...
+//! let step = |n: &u32| n * 3 % 10;
+//! assert_eq!(brent(3, step), Cycle { offset: 0, period: 4 });
+//! assert_eq!(nth_state(3, step, 1_000_000_001), 9);
+//! ```
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence of states loops
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that is part of the loop
+    pub offset: usize,
+    /// Length of the loop
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The smallest index with the same state as index `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.offset {
+            n
+        } else {
+            self.offset + (n - self.offset) % self.period
+        }
+    }
+}
+
+/// Floyd's tortoise and hare, using constant memory
+pub fn floyd<S: Clone + PartialEq>(start: S, step: impl Fn(&S) -> S) -> Cycle {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut offset = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+    Cycle { offset, period }
+}
+
+/// Brent's algorithm, using constant memory and fewer steps than [`floyd`]
+pub fn brent<S: Clone + PartialEq>(start: S, step: impl Fn(&S) -> S) -> Cycle {
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut offset = 0;
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+    Cycle { offset, period }
+}
+
+/// The state after `n` steps, skipping whole loops
+pub fn nth_state<S: Clone + PartialEq>(start: S, step: impl Fn(&S) -> S, n: usize) -> S {
+    let n = brent(start.clone(), &step).reduce(n);
+    (0..n).fold(start, |state, _| step(&state))
+}
+
+/// Every state of a sequence up to where it loops, remembered in a hash map
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    /// States from the start up to the end of the first loop
+    pub states: Vec<S>,
+    pub cycle: Cycle,
+}
+
+impl<S: Clone + Eq + Hash> History<S> {
+    /// Step from `start` until a state repeats
+    pub fn new(start: S, step: impl Fn(&S) -> S) -> Self {
+        let mut seen = HashMap::new();
+        let mut states = vec![];
+        let mut state = start;
+        loop {
+            if let Some(&offset) = seen.get(&state) {
+                let period = states.len() - offset;
+                return Self {
+                    states,
+                    cycle: Cycle { offset, period },
+                };
+            }
+            seen.insert(state.clone(), states.len());
+            let next = step(&state);
+            states.push(state);
+            state = next;
+        }
+    }
+
+    /// The state after `n` steps
+    pub fn nth(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+}
+
+#[test]
+fn test_cycle() {
+    // offset 3, then 10 -> 11 -> ... -> 16 -> 10
+    let step = |&n: &u32| match n {
+        0 => 7,
+        7 => 8,
+        8 => 10,
+        16 => 10,
+        n => n + 1,
+    };
+    let cycle = Cycle {
+        offset: 3,
+        period: 7,
+    };
+    assert_eq!(floyd(0, step), cycle);
+    assert_eq!(brent(0, step), cycle);
+
+    let history = History::new(0, step);
+    assert_eq!(history.cycle, cycle);
+    assert_eq!(history.states, [0, 7, 8, 10, 11, 12, 13, 14, 15, 16]);
+
+    let naive = |n| (0..n).fold(0, |s, _| step(&s));
+    for n in 0..40 {
+        assert_eq!(nth_state(0, step, n), naive(n));
+        assert_eq!(*history.nth(n), naive(n));
+    }
+    assert_eq!(nth_state(0, step, 1_000_000_003), 16);
+
+    // looping straight away
+    assert_eq!(
+        brent(1, |&n: &u8| n),
+        Cycle {
+            offset: 0,
+            period: 1
+        }
+    );
+    assert_eq!(
+        floyd(1, |&n: &u8| n),
+        Cycle {
+            offset: 0,
+            period: 1
+        }
+    );
+}
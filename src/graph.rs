@@ -0,0 +1,526 @@
+//! Algorithms on explicit graphs, built from adjacency lists.
+//!
+//! Nodes are anything `Copy + Ord`, e.g. `&str` names borrowed from the input,
+//! so building a graph doesn't allocate any strings.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use aoc::graph::Graph;
+//!
+//! let input = "AAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)";
+//! let graph = Graph::from_adjacency(input.lines().map(|line| {
+//!     let (node, next) = line.split_once(" = ").unwrap();
+//!     let (left, right) = next[1..next.len() - 1].split_once(", ").unwrap();
+//!     (node, [left, right])
+//! }));
+//! let (steps, path) = graph.shortest_path("AAA", |n| n == "ZZZ").unwrap();
+//! assert_eq!(steps, 2);
+//! assert_eq!(path, ["AAA", "CCC", "ZZZ"]);
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap, VecDeque},
+    ops::Add,
+};
+
+use eyre::Report;
+
+/// A directed graph with edges weighted by `W`
+#[derive(Debug, Clone)]
+pub struct Graph<N, W = u64> {
+    nodes: Vec<N>,
+    ids: BTreeMap<N, usize>,
+    edges: Vec<Vec<(usize, W)>>,
+}
+
+impl<N, W> Default for Graph<N, W> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            ids: BTreeMap::new(),
+            edges: vec![],
+        }
+    }
+}
+
+impl<N: Copy + Ord> Graph<N, u64> {
+    /// Create a graph where every edge has weight 1
+    pub fn from_adjacency<I>(adjacency: impl IntoIterator<Item = (N, I)>) -> Self
+    where
+        I: IntoIterator<Item = N>, {
+        Self::from_weighted_adjacency(
+            adjacency
+                .into_iter()
+                .map(|(node, next)| (node, next.into_iter().map(|n| (n, 1)))),
+        )
+    }
+}
+
+impl<N: Copy + Ord, W> Graph<N, W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_weighted_adjacency<I>(adjacency: impl IntoIterator<Item = (N, I)>) -> Self
+    where
+        I: IntoIterator<Item = (N, W)>, {
+        let mut graph = Self::new();
+        for (node, next) in adjacency {
+            graph.add_node(node);
+            for (to, weight) in next {
+                graph.add_edge(node, to, weight);
+            }
+        }
+        graph
+    }
+
+    /// Add `node` if it isn't in the graph yet, returns its id.
+    pub fn add_node(&mut self, node: N) -> usize {
+        *self.ids.entry(node).or_insert_with(|| {
+            self.nodes.push(node);
+            self.edges.push(vec![]);
+            self.nodes.len() - 1
+        })
+    }
+
+    /// Add an edge from `from` to `to`, adding the nodes if needed.
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.edges[from].push((to, weight));
+    }
+
+    /// Add edges both ways between `a` and `b`
+    pub fn add_undirected_edge(&mut self, a: N, b: N, weight: W)
+    where
+        W: Clone, {
+        self.add_edge(a, b, weight.clone());
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: N) -> bool {
+        self.ids.contains_key(&node)
+    }
+
+    /// All nodes, in the order they were added
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// All edges as `(from, to, weight)`
+    pub fn edges(&self) -> impl Iterator<Item = (N, N, &W)> {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(move |(from, edges)| {
+                edges
+                    .iter()
+                    .map(move |(to, w)| (self.nodes[from], self.nodes[*to], w))
+            })
+    }
+
+    /// Nodes reachable from `node` in one step, with the weight of the edge
+    pub fn neighbours(&self, node: N) -> impl Iterator<Item = (N, &W)> {
+        let edges = self.ids.get(&node).map_or(&[][..], |&id| &self.edges[id]);
+        edges.iter().map(|(to, w)| (self.nodes[*to], w))
+    }
+
+    /// Number of steps from `start` to every node reachable from it
+    pub fn bfs(&self, start: N) -> BTreeMap<N, usize> {
+        let mut distances = BTreeMap::new();
+        let Some(&start) = self.ids.get(&start) else {
+            return distances;
+        };
+        let mut seen = vec![false; self.len()];
+        seen[start] = true;
+        let mut queue = VecDeque::from([(start, 0)]);
+        while let Some((node, distance)) = queue.pop_front() {
+            distances.insert(self.nodes[node], distance);
+            for &(next, _) in &self.edges[node] {
+                if !std::mem::replace(&mut seen[next], true) {
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    /// Dijkstra from `start` until a node matching `goal` is reached,
+    /// returning the cost and the nodes of the path.
+    pub fn shortest_path(&self, start: N, mut goal: impl FnMut(N) -> bool) -> Option<(W, Vec<N>)>
+    where
+        W: Copy + Ord + Add<Output = W> + Default, {
+        let search = self.dijkstra(start, |id| goal(self.nodes[id]));
+        let end = search.reached?;
+        let mut path = vec![self.nodes[end]];
+        let mut current = end;
+        while let Some(prev) = search.previous[current] {
+            path.push(self.nodes[prev]);
+            current = prev;
+        }
+        path.reverse();
+        Some((search.costs[end]?, path))
+    }
+
+    /// Dijkstra from `start`, the cost to reach every reachable node
+    pub fn distances(&self, start: N) -> BTreeMap<N, W>
+    where
+        W: Copy + Ord + Add<Output = W> + Default, {
+        self.dijkstra(start, |_| false)
+            .costs
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, cost)| Some((self.nodes[id], cost?)))
+            .collect()
+    }
+
+    fn dijkstra(&self, start: N, mut stop: impl FnMut(usize) -> bool) -> Search<W>
+    where
+        W: Copy + Ord + Add<Output = W> + Default, {
+        let mut search = Search {
+            costs: vec![None; self.len()],
+            previous: vec![None; self.len()],
+            reached: None,
+        };
+        let Some(&start) = self.ids.get(&start) else {
+            return search;
+        };
+        search.costs[start] = Some(W::default());
+        let mut heap = BinaryHeap::from([Reverse((W::default(), start))]);
+        while let Some(Reverse((cost, node))) = heap.pop() {
+            if search.costs[node].is_some_and(|c| c < cost) {
+                continue;
+            }
+            if stop(node) {
+                search.reached = Some(node);
+                break;
+            }
+            for &(next, weight) in &self.edges[node] {
+                let cost = cost + weight;
+                if search.costs[next].is_none_or(|c| cost < c) {
+                    search.costs[next] = Some(cost);
+                    search.previous[next] = Some(node);
+                    heap.push(Reverse((cost, next)));
+                }
+            }
+        }
+        search
+    }
+
+    /// Order the nodes so every edge goes from an earlier to a later node.
+    ///
+    /// Fails if the graph has a cycle.
+    pub fn topological_sort(&self) -> Result<Vec<N>, Report> {
+        let mut incoming = vec![0; self.len()];
+        for &(to, _) in self.edges.iter().flatten() {
+            incoming[to] += 1;
+        }
+        let mut queue: VecDeque<_> = (0..self.len()).filter(|&n| incoming[n] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(self.nodes[node]);
+            for &(next, _) in &self.edges[node] {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+        if order.len() != self.len() {
+            eyre::bail!(
+                "graph has a cycle, only {} of {} nodes could be sorted",
+                order.len(),
+                self.len()
+            );
+        }
+        Ok(order)
+    }
+
+    /// Strongly connected components with Tarjan's algorithm.
+    ///
+    /// Components come in reverse topological order, i.e. no edge goes from a
+    /// component to one before it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut lowlink = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut next_index = 0;
+
+        for root in 0..self.len() {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // (node, next edge to look at), instead of recursing
+            let mut calls = vec![(root, 0)];
+            index[root] = next_index;
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((node, edge)) = calls.last_mut() {
+                let node = *node;
+                if let Some(&(next, _)) = self.edges[node].get(*edge) {
+                    *edge += 1;
+                    if index[next] == usize::MAX {
+                        index[next] = next_index;
+                        lowlink[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        calls.push((next, 0));
+                    } else if on_stack[next] {
+                        lowlink[node] = lowlink[node].min(index[next]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+                if lowlink[node] == index[node] {
+                    let mut component = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(self.nodes[member]);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Components of the graph when ignoring the direction of edges
+    pub fn connected_components(&self) -> Vec<Vec<N>> {
+        fn find(parent: &mut [usize], mut node: usize) -> usize {
+            while parent[node] != node {
+                parent[node] = parent[parent[node]];
+                node = parent[node];
+            }
+            node
+        }
+
+        let mut parent: Vec<usize> = (0..self.len()).collect();
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges {
+                let (a, b) = (find(&mut parent, from), find(&mut parent, to));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+        let mut components = BTreeMap::<usize, Vec<N>>::new();
+        for node in 0..self.len() {
+            let root = find(&mut parent, node);
+            components.entry(root).or_default().push(self.nodes[node]);
+        }
+        components.into_values().collect()
+    }
+
+    /// Global minimum cut with Stoer-Wagner, for an undirected graph.
+    ///
+    /// Returns the total weight of the cut edges and the nodes on one side of
+    /// the cut, or `None` if the graph has less than two nodes. Parallel edges
+    /// add up, and an edge only added one way counts as undirected.
+    pub fn min_cut(&self) -> Option<(W, Vec<N>)>
+    where
+        W: Copy + Ord + Add<Output = W> + Default, {
+        if self.len() < 2 {
+            return None;
+        }
+        // sum up parallel edges per direction, undirected edges are stored
+        // both ways so only one direction counts
+        let mut directed: Vec<HashMap<usize, W>> = vec![HashMap::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, weight) in edges {
+                if from != to {
+                    let w = directed[from].entry(to).or_default();
+                    *w = *w + weight;
+                }
+            }
+        }
+        let mut adjacency = directed.clone();
+        for (from, edges) in directed.iter().enumerate() {
+            for (&to, &weight) in edges {
+                let w = adjacency[to].entry(from).or_default();
+                *w = (*w).max(weight);
+            }
+        }
+        // the original nodes merged into each node
+        let mut merged: Vec<Vec<usize>> = (0..self.len()).map(|n| vec![n]).collect();
+        let mut active: Vec<usize> = (0..self.len()).collect();
+        let mut best: Option<(W, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // maximum adjacency ordering, from the first active node
+            let mut weight = vec![W::default(); self.len()];
+            let mut added = vec![false; self.len()];
+            let mut heap = BinaryHeap::from([(W::default(), active[0])]);
+            let (mut s, mut t) = (active[0], active[0]);
+            let mut cut = W::default();
+            let mut count = 0;
+            while let Some((w, node)) = heap.pop() {
+                if added[node] || w < weight[node] {
+                    continue;
+                }
+                added[node] = true;
+                (s, t, cut) = (t, node, w);
+                count += 1;
+                if count == active.len() {
+                    break;
+                }
+                for (&next, &edge) in &adjacency[node] {
+                    if !added[next] {
+                        weight[next] = weight[next] + edge;
+                        heap.push((weight[next], next));
+                    }
+                }
+            }
+            if count < active.len() {
+                // not connected, no edges need to be cut
+                let side = active
+                    .iter()
+                    .filter(|&&n| added[n])
+                    .flat_map(|&n| merged[n].iter().copied())
+                    .collect();
+                best = Some((W::default(), side));
+                break;
+            }
+
+            if best.as_ref().is_none_or(|(b, _)| cut < *b) {
+                best = Some((cut, merged[t].clone()));
+            }
+
+            // merge t into s
+            let t_edges = std::mem::take(&mut adjacency[t]);
+            for (next, edge) in t_edges {
+                adjacency[next].remove(&t);
+                if next != s {
+                    let w = adjacency[s].entry(next).or_default();
+                    *w = *w + edge;
+                    let w = adjacency[next].entry(s).or_default();
+                    *w = *w + edge;
+                }
+            }
+            let t_merged = std::mem::take(&mut merged[t]);
+            merged[s].extend(t_merged);
+            active.retain(|&n| n != t);
+        }
+        best.map(|(cut, side)| (cut, side.into_iter().map(|n| self.nodes[n]).collect()))
+    }
+}
+
+/// State of a Dijkstra search, by node id
+struct Search<W> {
+    costs: Vec<Option<W>>,
+    previous: Vec<Option<usize>>,
+    /// The node the search stopped at
+    reached: Option<usize>,
+}
+
+#[test]
+fn test_paths() {
+    let mut graph = Graph::new();
+    graph.add_edge("a", "b", 7);
+    graph.add_edge("a", "c", 2);
+    graph.add_edge("c", "b", 3);
+    graph.add_edge("b", "d", 1);
+    graph.add_node("e");
+    assert_eq!(graph.len(), 5);
+    assert_eq!(
+        graph.shortest_path("a", |n| n == "d"),
+        Some((6, vec!["a", "c", "b", "d"]))
+    );
+    assert_eq!(graph.shortest_path("a", |n| n == "e"), None);
+    assert_eq!(
+        graph.distances("a"),
+        BTreeMap::from([("a", 0), ("b", 5), ("c", 2), ("d", 6)])
+    );
+    assert_eq!(
+        graph.bfs("a"),
+        BTreeMap::from([("a", 0), ("b", 1), ("c", 1), ("d", 2)])
+    );
+    assert_eq!(graph.topological_sort().unwrap(), ["a", "e", "c", "b", "d"]);
+    graph.add_edge("d", "a", 1);
+    assert!(graph.topological_sort().is_err());
+}
+
+#[test]
+fn test_components() {
+    let graph = Graph::from_adjacency([
+        (1, vec![2]),
+        (2, vec![3]),
+        (3, vec![1, 4]),
+        (4, vec![]),
+        (5, vec![6]),
+        (6, vec![5]),
+    ]);
+    let mut sccs = graph.strongly_connected_components();
+    assert_eq!(sccs[0], [4]);
+    sccs.iter_mut().for_each(|c| c.sort());
+    sccs.sort();
+    assert_eq!(sccs, [vec![1, 2, 3], vec![4], vec![5, 6]]);
+    assert_eq!(graph.connected_components(), [vec![1, 2, 3, 4], vec![5, 6]]);
+}
+
+#[test]
+fn test_min_cut() {
+    // two triangles joined by a single edge
+    let mut graph = Graph::new();
+    for (a, b) in [
+        ("a", "b"),
+        ("b", "c"),
+        ("c", "a"),
+        ("x", "y"),
+        ("y", "z"),
+        ("z", "x"),
+        ("c", "x"),
+    ] {
+        graph.add_undirected_edge(a, b, 1u32);
+    }
+    let (cut, mut side) = graph.min_cut().unwrap();
+    assert_eq!(cut, 1);
+    side.sort();
+    assert!(
+        side == ["a", "b", "c"] || side == ["x", "y", "z"],
+        "{side:?}"
+    );
+
+    // a doubled bridge costs twice as much, cutting off a corner is cheaper
+    graph.add_undirected_edge("c", "x", 2);
+    assert_eq!(graph.min_cut().map(|(cut, _)| cut), Some(2));
+    graph.add_undirected_edge("a", "b", 1);
+    graph.add_undirected_edge("b", "c", 1);
+    graph.add_undirected_edge("c", "a", 1);
+    graph.add_undirected_edge("x", "y", 1);
+    let (cut, mut side) = graph.min_cut().unwrap();
+    assert_eq!(cut, 2);
+    side.sort();
+    assert!(
+        side == ["z"] || side == ["a", "b", "c", "x", "y"],
+        "{side:?}"
+    );
+
+    let mut one_way = Graph::new();
+    one_way.add_edge(1, 2, 5u32);
+    one_way.add_edge(1, 2, 5);
+    assert_eq!(one_way.min_cut().map(|(cut, _)| cut), Some(10));
+
+    graph.add_node("alone");
+    assert_eq!(graph.min_cut().map(|(cut, _)| cut), Some(0));
+    assert_eq!(Graph::<&str>::new().min_cut(), None);
+}
@@ -1,18 +1,25 @@
-#![feature(
-    get_many_mut,
-    slice_ptr_get,
-    type_alias_impl_trait,
-    impl_trait_in_assoc_type
-)]
+pub mod alloc;
 pub mod aoc;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod cycle;
+pub mod graph;
+pub mod math;
+pub mod ocr;
 pub mod parts;
+pub mod registry;
+pub mod search;
 pub mod test_util;
 pub mod utils;
 
+use std::time::{Duration, Instant};
+
 pub use crate::aoc::{Aoc, Level};
 use eyre::Report;
+use serde::Serialize;
 pub use utils::*;
 
+pub use inventory;
 pub use owo_colors;
 
 pub trait Solver<const YEAR: u32, const DAY: u32, const PART: u32> {
@@ -26,6 +33,112 @@ pub trait Solver<const YEAR: u32, const DAY: u32, const PART: u32> {
     fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report>;
 }
 
+/// Solves both parts of a day from a single parse.
+///
+/// Implementing this gives [`Solver`] impls for [`Part1`](parts::Part1) and
+/// [`Part2`](parts::Part2), so the day still works with [`solve_with_input`]
+/// and the registry. `State` is whatever part 1 computed that part 2 can
+/// reuse, use `()` if there is nothing to share.
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::{parts::*, DaySolver};
+/// use eyre::Report;
+///
+/// pub struct Solution;
+///
+/// impl DaySolver<Year2022, Day1> for Solution {
+///     type Input<'a> = Vec<Vec<u32>>;
+///     type State = Vec<u32>;
+///     type Output1 = u32;
+///     type Output2 = u32;
+///
+///     fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
+///         input
+///             .split("\n\n")
+///             .map(|elf| elf.lines().map(|l| Ok(l.parse()?)).collect())
+///             .collect()
+///     }
+///
+///     fn solve_first(input: &Self::Input<'_>) -> Result<(u32, Vec<u32>), Report> {
+///         let mut totals: Vec<u32> = input.iter().map(|elf| elf.iter().sum()).collect();
+///         totals.sort_unstable_by(|a, b| b.cmp(a));
+///         Ok((totals[0], totals))
+///     }
+///
+///     fn solve_second(_: &Self::Input<'_>, totals: Vec<u32>) -> Result<u32, Report> {
+///         Ok(totals.iter().take(3).sum())
+///     }
+/// }
+///
+/// let input = "1\n2\n\n4\n\n3\n\n1";
+/// assert_eq!(aoc::solve_both_with_input::<Solution, Year2022, Day1>(input)?, (4, 10));
+/// assert_eq!(aoc::solve_with_input::<Solution, Year2022, Day1, Part2>(input)?, 10);
+/// # Ok::<(), Report>(())
+/// ```
+pub trait DaySolver<const YEAR: u32, const DAY: u32> {
+    type Input<'a>
+    where
+        Self: 'a;
+    type State;
+    type Output1: std::fmt::Display;
+    type Output2: std::fmt::Display;
+
+    fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report>;
+
+    fn solve_first(input: &Self::Input<'_>) -> Result<(Self::Output1, Self::State), Report>;
+
+    fn solve_second(input: &Self::Input<'_>, state: Self::State) -> Result<Self::Output2, Report>;
+}
+
+impl<S: DaySolver<YEAR, DAY>, const YEAR: u32, const DAY: u32> Solver<YEAR, DAY, { parts::Part1 }>
+    for S
+{
+    type Input<'a>
+        = S::Input<'a>
+    where
+        Self: 'a;
+    type Output = S::Output1;
+
+    fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
+        S::generate_input(input)
+    }
+
+    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
+        S::solve_first(input).map(|(output, _)| output)
+    }
+}
+
+impl<S: DaySolver<YEAR, DAY>, const YEAR: u32, const DAY: u32> Solver<YEAR, DAY, { parts::Part2 }>
+    for S
+{
+    type Input<'a>
+        = S::Input<'a>
+    where
+        Self: 'a;
+    type Output = S::Output2;
+
+    fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
+        S::generate_input(input)
+    }
+
+    /// Runs part 1 first to get the state part 2 needs
+    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
+        let (_, state) = S::solve_first(input)?;
+        S::solve_second(input, state)
+    }
+}
+
+/// Parse `input` once and solve both parts
+pub fn solve_both_with_input<S: DaySolver<YEAR, DAY>, const YEAR: u32, const DAY: u32>(
+    input: &str,
+) -> Result<(S::Output1, S::Output2), Report> {
+    let input = S::generate_input(input)?;
+    let (first, state) = S::solve_first(&input)?;
+    Ok((first, S::solve_second(&input, state)?))
+}
+
 pub fn solve_with_input<
     S: Solver<YEAR, DAY, PART>,
     const YEAR: u32,
@@ -37,3 +150,35 @@ pub fn solve_with_input<
     let input = S::generate_input(input)?;
     S::solve(&input)
 }
+
+/// Time spent in [`Solver::generate_input`] and [`Solver::solve`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Like [`solve_with_input`], but also measures how long parsing and solving
+/// took.
+pub fn solve_with_input_timed<
+    S: Solver<YEAR, DAY, PART>,
+    const YEAR: u32,
+    const DAY: u32,
+    const PART: u32,
+>(
+    input: &str,
+) -> Result<(S::Output, Timings), Report> {
+    let start = Instant::now();
+    let input = S::generate_input(input)?;
+    let parse = start.elapsed();
+    let start = Instant::now();
+    let output = S::solve(&input)?;
+    let solve = start.elapsed();
+    Ok((output, Timings { parse, solve }))
+}
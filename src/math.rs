@@ -0,0 +1,544 @@
+//! Math that keeps coming back: gcd and lcm, modular arithmetic, the chinese
+//! remainder theorem, discrete logarithms, integer roots and binary search.
+//!
+//! Modular products go through `u128` so any `u64` modulus works.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use aoc::math::{crt, lcm_all, Lcg};
+//!
+//! // buses leaving every 7, 13 and 59 minutes
+//! assert_eq!(lcm_all([7, 13, 59]), Some(5369));
+//!
+//! // a timestamp t where bus 7 leaves at t, bus 13 at t + 1 and bus 59 at t + 4
+//! assert_eq!(crt([(0, 7), (-1, 13), (-4, 59)]), Some((350, 5369)));
+//!
+//! // deal into new stack, then cut 3, on a deck of 10 cards
+//! let shuffle = Lcg::new(9, 9, 10).then(Lcg::new(1, 7, 10));
+//! assert_eq!(shuffle.apply(0), 6);
+//! assert_eq!(shuffle.pow(2).apply(0), shuffle.apply(shuffle.apply(0)));
+//! ```
+
+use std::{cmp::Ordering, collections::HashMap, ops::RangeBounds};
+
+use crate::utils::{interval::Int, IntervalSet};
+
+pub mod poly;
+
+/// Greatest common divisor, `gcd(0, 0)` is 0
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, `None` if it doesn't fit in a `u64`
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Greatest common divisor of all the numbers, 0 if there are none
+pub fn gcd_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// Least common multiple of all the numbers, 1 if there are none
+pub fn lcm_all(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1, lcm)
+}
+
+/// Extended Euclid, returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+///
+/// Panics if `g` is `2^63`, which doesn't fit in an `i64`. That only happens
+/// for `i64::MIN` with 0 or itself.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a.into(), b.into());
+    let g = i64::try_from(g).expect("gcd of 2^63 overflows an i64");
+    // |x| <= |b| / g and |y| <= |a| / g, so they fit once g does
+    (g, x as i64, y as i64)
+}
+
+/// [`extended_gcd`] wide enough for any `u64` operands
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (r0, x0, y0) = (-r0, -x0, -y0);
+    }
+    (r0, x0, y0)
+}
+
+/// `a * b % m`
+pub fn mod_mul(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `base ^ exp % m` by squaring, panics if `m` is 0
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    assert!(m > 0, "modulus of 0");
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// `x` with `a * x % m == 1`, if `a` and `m` are coprime and `m` isn't 0
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd_wide((a % m).into(), m.into());
+    (g == 1).then(|| x.rem_euclid(m.into()) as u64)
+}
+
+/// Solve `x ≡ r (mod m)` for every `(r, m)`.
+///
+/// The moduli don't have to be coprime. Returns the smallest non-negative
+/// solution with the lcm of the moduli, `None` if the congruences contradict
+/// each other, a modulus is 0 or the lcm doesn't fit in a `u64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, u64)>) -> Option<(u64, u64)> {
+    congruences
+        .into_iter()
+        .try_fold((0u64, 1u64), |(r1, m1), (r2, m2)| {
+            if m2 == 0 {
+                return None;
+            }
+            let r2 = i128::from(r2).rem_euclid(m2.into()) as u64;
+            let g = gcd(m1, m2);
+            let diff = r2 as i128 - r1 as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+            let m = lcm(m1, m2)?;
+            // r1 + m1 * k ≡ r2 (mod m2), so k ≡ diff / g * (m1 / g)^-1 (mod m2 / g)
+            let step = m2 / g;
+            let k = if step == 1 {
+                0
+            } else {
+                let inverse = mod_inverse(m1 / g % step, step)?;
+                let diff = (diff / g as i128).rem_euclid(step as i128) as u64;
+                mod_mul(diff, inverse, step)
+            };
+            let r = (r1 as u128 + m1 as u128 * k as u128) % m as u128;
+            Some((r as u64, m))
+        })
+}
+
+/// Smallest `x` with `base ^ x % m == target % m`, by baby-step giant-step.
+///
+/// `base` and `m` don't have to be coprime. `None` if there is no such `x` or
+/// `m` is 0.
+pub fn discrete_log(base: u64, target: u64, mut m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    if m == 1 {
+        return Some(0);
+    }
+    let (base, mut target) = (base % m, target % m);
+    // take out common factors until base and m are coprime, solving
+    // factor * base ^ (x - skipped) ≡ target (mod m)
+    let (mut factor, mut skipped) = (1, 0);
+    loop {
+        let g = gcd(base, m);
+        if g == 1 {
+            break;
+        }
+        if target == factor {
+            return Some(skipped);
+        }
+        if target % g != 0 {
+            return None;
+        }
+        (target, m) = (target / g, m / g);
+        factor = mod_mul(factor, base / g, m);
+        skipped += 1;
+    }
+
+    let n = isqrt(m) + 1;
+    // baby steps target * base ^ j, keeping the largest j for the smallest x
+    let mut baby = HashMap::new();
+    let mut value = target;
+    for j in 0..=n {
+        baby.insert(value, j);
+        value = mod_mul(value, base, m);
+    }
+    // giant steps factor * base ^ (n * i)
+    let giant = mod_pow(base, n, m);
+    let mut value = factor;
+    for i in 1..=n {
+        value = mod_mul(value, giant, m);
+        if let Some(&j) = baby.get(&value) {
+            return Some(n * i - j + skipped);
+        }
+    }
+    None
+}
+
+/// The linear congruential function `x -> (a * x + b) % m`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lcg {
+    pub a: u64,
+    pub b: u64,
+    pub m: u64,
+}
+
+impl Lcg {
+    /// `x -> (a * x + b) % m`, panics if `m` is 0
+    pub fn new(a: u64, b: u64, m: u64) -> Self {
+        assert!(m > 0, "modulus of 0");
+        Self {
+            a: a % m,
+            b: b % m,
+            m,
+        }
+    }
+
+    /// `x -> x`
+    pub fn identity(m: u64) -> Self {
+        Self::new(1, 0, m)
+    }
+
+    pub fn apply(&self, x: u64) -> u64 {
+        (mod_mul(self.a, x, self.m) + self.b) % self.m
+    }
+
+    /// Apply `self`, then `next`
+    pub fn then(self, next: Self) -> Self {
+        assert_eq!(self.m, next.m, "composing functions with different moduli");
+        Self {
+            a: mod_mul(next.a, self.a, self.m),
+            b: (mod_mul(next.a, self.b, self.m) + next.b) % self.m,
+            m: self.m,
+        }
+    }
+
+    /// Apply `self` `n` times
+    pub fn pow(self, mut n: u64) -> Self {
+        let (mut result, mut square) = (Self::identity(self.m), self);
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.then(square);
+            }
+            square = square.then(square);
+            n >>= 1;
+        }
+        result
+    }
+
+    /// The function undoing `self`, if `a` is invertible
+    pub fn inverse(self) -> Option<Self> {
+        let a = mod_inverse(self.a, self.m)?;
+        Some(Self {
+            a,
+            b: mod_mul(a, self.m - self.b, self.m),
+            m: self.m,
+        })
+    }
+}
+
+/// Integer square root, the largest `r` with `r * r <= n`
+pub fn isqrt(n: u64) -> u64 {
+    isqrt_u128(n.into()) as u64
+}
+
+/// Integer square root, the largest `r` with `r * r <= n`
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // refine the float estimate with Newton, then fix the last few ulps
+    let mut r = ((n as f64).sqrt() as u128).max(1);
+    for _ in 0..2 {
+        r = (r + n / r) / 2;
+    }
+    while r.checked_mul(r).is_none_or(|square| square > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|square| square <= n) {
+        r += 1;
+    }
+    r
+}
+
+/// The integers in `lo..=hi` that fit in an `i64`
+fn clamped(lo: i128, hi: i128) -> IntervalSet<i64> {
+    let lo = lo.max(i64::MIN.into()) as i64;
+    let hi = hi.min(i64::MAX.into());
+    if hi < lo.into() {
+        return IntervalSet::new();
+    }
+    IntervalSet::from_range(lo..=hi as i64)
+}
+
+/// Every integer `x` with `a * x^2 + b * x + c > 0`, computed exactly.
+///
+/// Panics if `b^2 - 4ac` doesn't fit in an `i128`.
+///
+/// # Examples
+///
+/// ```rust
+/// // 2023 day 6, holding the button x ms of 30 to beat 200 mm:
+/// // x (30 - x) > 200
+/// let wins = aoc::math::quadratic_positive(-1, 30, -200);
+/// assert_eq!(wins.ranges().collect::<Vec<_>>(), [11..=19]);
+/// ```
+pub fn quadratic_positive(a: i64, b: i64, c: i64) -> IntervalSet<i64> {
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+    if a == 0 {
+        return match b.cmp(&0) {
+            // b x > -c
+            Ordering::Greater => clamped(-(c - 1).div_euclid(b), i128::MAX),
+            // -b x < c
+            Ordering::Less => clamped(i128::MIN, (c - 1).div_euclid(-b)),
+            Ordering::Equal if c > 0 => IntervalSet::from_range(..),
+            Ordering::Equal => IntervalSet::new(),
+        };
+    }
+    // flip the parabola to open upwards, positive values of the original are
+    // then either strictly between the roots or outside of them
+    let opens_down = a < 0;
+    let (a, b, c) = if opens_down { (-a, -b, -c) } else { (a, b, c) };
+    let between = |x: i128| {
+        let y = a * x * x + b * x + c;
+        if opens_down {
+            y < 0
+        } else {
+            y <= 0
+        }
+    };
+    let discriminant = (4 * a)
+        .checked_mul(c)
+        .and_then(|ac| (b * b).checked_sub(ac))
+        .expect("discriminant overflows an i128");
+
+    let mut inside = IntervalSet::new();
+    if discriminant >= 0 {
+        let root = isqrt_u128(discriminant as u128) as i128;
+        // within one of the real roots
+        let (left, right) = ((-b - root).div_euclid(2 * a), (-b + root).div_euclid(2 * a));
+        let lo = (left - 2..=left + 3).find(|&x| between(x));
+        let hi = (right - 3..=right + 2).rev().find(|&x| between(x));
+        if let (Some(lo), Some(hi)) = (lo, hi) {
+            inside = clamped(lo, hi);
+        }
+    }
+    if opens_down {
+        inside
+    } else {
+        inside.complement(..)
+    }
+}
+
+/// The first value of `range` where `pred` is false, `pred` being true for
+/// everything before it and false after.
+///
+/// `None` if `pred` is true for all of `range`.
+pub fn partition_point<T: Int>(
+    range: impl RangeBounds<T>,
+    mut pred: impl FnMut(T) -> bool,
+) -> Option<T> {
+    let (start, end) = IntervalSet::from_range(range).ranges().next()?.into_inner();
+    let (mut lo, mut hi) = (start.to_i128(), end.to_i128() + 1);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(T::from_i128(mid).expect("mid is in range")) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    T::from_i128(lo).filter(|&first| first <= end)
+}
+
+/// A value of `range` where `cmp` gives `Equal`, `cmp` being `Less` before
+/// and `Greater` after.
+pub fn binary_search<T: Int>(
+    range: impl RangeBounds<T>,
+    mut cmp: impl FnMut(T) -> Ordering,
+) -> Option<T> {
+    partition_point(range, |x| cmp(x) == Ordering::Less).filter(|&x| cmp(x) == Ordering::Equal)
+}
+
+/// The first value from `start` where `pred` is false, when there's no known
+/// upper bound.
+///
+/// Probes `start + 1`, `start + 3`, `start + 7` and so on until `pred` fails,
+/// then binary searches the last step. `None` if `pred` holds up to `T::MAX`.
+pub fn exponential_search<T: Int>(start: T, mut pred: impl FnMut(T) -> bool) -> Option<T> {
+    if !pred(start) {
+        return Some(start);
+    }
+    let max = T::MAX.to_i128();
+    let (mut last_true, mut step) = (start.to_i128(), 1i128);
+    loop {
+        let probe = (last_true + step).min(max);
+        let value = T::from_i128(probe).expect("probe is in range");
+        if !pred(value) {
+            return partition_point(T::from_i128(last_true + 1)?..=value, pred);
+        }
+        if probe == max {
+            return None;
+        }
+        (last_true, step) = (probe, step * 2);
+    }
+}
+
+#[test]
+fn test_gcd() {
+    assert_eq!(gcd(12, 18), 6);
+    assert_eq!(gcd(0, 5), 5);
+    assert_eq!(gcd_all([]), 0);
+    assert_eq!(gcd_all([12, 18, 8]), 2);
+    assert_eq!(lcm(4, 6), Some(12));
+    assert_eq!(lcm(0, 6), Some(0));
+    assert_eq!(lcm_all([]), Some(1));
+    assert_eq!(lcm_all([u64::MAX, 2]), None);
+    assert_eq!(lcm_all([1 << 40, 1 << 62]), Some(1 << 62));
+
+    for (a, b) in [
+        (240, 46),
+        (-240, 46),
+        (0, 7),
+        (7, 0),
+        (i64::MAX, i64::MAX - 1),
+    ] {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g as u64, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+    }
+    assert_eq!(extended_gcd(i64::MIN, 1).0, 1);
+    assert!(std::panic::catch_unwind(|| extended_gcd(i64::MIN, 0)).is_err());
+}
+
+#[test]
+fn test_modular() {
+    let m = (1 << 61) - 1;
+    assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+    assert_eq!(mod_pow(2, 61, m), 1);
+    assert_eq!(mod_pow(5, 0, 1), 0);
+    assert_eq!(mod_inverse(3, 11), Some(4));
+    assert_eq!(mod_inverse(4, 8), None);
+    assert_eq!(mod_inverse(2, u64::MAX), Some(u64::MAX / 2 + 1));
+    let inverse = mod_inverse(123_456_789, m).unwrap();
+    assert_eq!(mod_mul(inverse, 123_456_789, m), 1);
+    assert_eq!(mod_inverse(3, 0), None);
+    assert!(std::panic::catch_unwind(|| mod_pow(2, 3, 0)).is_err());
+
+    assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+    assert_eq!(crt([(1, 4), (2, 6)]), None);
+    assert_eq!(crt([]), Some((0, 1)));
+    assert_eq!(crt([(3, 5), (1, 0)]), None);
+    let big = (1 << 63) + 5;
+    assert_eq!(crt([(-1, big)]), Some((big - 1, big)));
+    assert_eq!(crt([(7, big), (0, 1)]), Some((7, big)));
+    // 2020 day 13 example
+    let buses = [(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)];
+    assert_eq!(
+        crt(buses.map(|(bus, offset)| (-offset, bus))),
+        Some((1068781, 3162341))
+    );
+
+    let naive =
+        |base: u64, target: u64, m: u64| (0..2 * m).find(|&x| mod_pow(base, x, m) == target % m);
+    for m in 1..40 {
+        for base in 0..m {
+            for target in 0..m {
+                assert_eq!(
+                    discrete_log(base, target, m),
+                    naive(base, target, m),
+                    "{base}^x = {target} mod {m}"
+                );
+            }
+        }
+    }
+    // 2020 day 25 example
+    assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+    assert_eq!(discrete_log(7, 1, 0), None);
+}
+
+#[test]
+fn test_lcg() {
+    let m = 10007;
+    let f = Lcg::new(4, 5, m);
+    let g = Lcg::new(m - 1, m - 1, m);
+    assert_eq!(f.then(g).apply(3), g.apply(f.apply(3)));
+    let naive = (0..1000).fold(17, |x, _| f.apply(x));
+    assert_eq!(f.pow(1000).apply(17), naive);
+    assert_eq!(f.inverse().unwrap().apply(f.apply(42)), 42);
+    assert_eq!(Lcg::new(2, 1, 4).inverse(), None);
+    assert!(std::panic::catch_unwind(|| Lcg::new(1, 0, 0)).is_err());
+}
+
+#[test]
+fn test_roots() {
+    for n in (0..10_000u64).chain([u64::MAX, u64::MAX - 1, (1 << 32) * ((1 << 32) - 1)]) {
+        let r = isqrt(n) as u128;
+        assert!(
+            r * r <= n as u128 && (r + 1) * (r + 1) > n as u128,
+            "isqrt({n})"
+        );
+    }
+    assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+    assert_eq!(isqrt_u128((1 << 100) - 1), (1 << 50) - 1);
+    assert_eq!(isqrt_u128(1 << 100), 1 << 50);
+
+    let naive = |a: i64, b: i64, c: i64| -> Vec<i64> {
+        (-60..=60).filter(|x| a * x * x + b * x + c > 0).collect()
+    };
+    for a in -3..=3 {
+        for b in -8..=8 {
+            for c in -12..=12 {
+                let set = quadratic_positive(a, b, c);
+                let found: Vec<i64> = (-60..=60).filter(|&x| set.contains(x)).collect();
+                assert_eq!(found, naive(a, b, c), "{a}x^2 + {b}x + {c} > 0");
+            }
+        }
+    }
+    // 2023 day 6 example, part 2
+    assert_eq!(quadratic_positive(-1, 71530, -940200).len(), 71503);
+    assert_eq!(quadratic_positive(1, 0, 1).len(), 1 << 64);
+    assert!(quadratic_positive(-1, 0, -1).is_empty());
+}
+
+#[test]
+fn test_binary_search() {
+    assert_eq!(partition_point(0..100, |x: u32| x * x < 50), Some(8));
+    assert_eq!(partition_point(0..5, |_: u8| true), None);
+    assert_eq!(partition_point(0..=u8::MAX, |x| x < 255), Some(255));
+    assert_eq!(partition_point(i64::MIN.., |x| x < -5), Some(-5));
+    assert_eq!(partition_point(5..5, |_: i32| false), None);
+
+    assert_eq!(binary_search(0..1000, |x: u64| (x * x).cmp(&529)), Some(23));
+    assert_eq!(binary_search(0..1000, |x: u64| (x * x).cmp(&530)), None);
+
+    let mut probes = 0;
+    let first = exponential_search(10u64, |x| {
+        probes += 1;
+        x < 1_000_000
+    });
+    assert_eq!(first, Some(1_000_000));
+    assert!(probes < 50);
+    assert_eq!(exponential_search(3u8, |x| x < 3), Some(3));
+    assert_eq!(exponential_search(3u8, |_| true), None);
+    assert_eq!(exponential_search(-3i8, |x| x < 127), Some(127));
+}
@@ -0,0 +1,384 @@
+//! Polynomial sequences: extrapolating by finite differences and exact
+//! interpolation through points.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use aoc::math::poly::{self, Polynomial, Rational};
+//!
+//! // 2023 day 9
+//! let history = [10, 13, 16, 21, 30, 45];
+//! assert_eq!(poly::degree(&history), Some(3));
+//! assert_eq!(poly::next(&history), Some(68));
+//! assert_eq!(poly::previous(&history), Some(5));
+//!
+//! // a quadratic sampled every 131 steps, evaluated way out
+//! let quadratic = Polynomial::interpolate(&[(65, 3889), (196, 34504), (327, 95591)]);
+//! assert_eq!(quadratic.degree(), Some(2));
+//! let far = quadratic.eval(26501365).and_then(Rational::to_integer);
+//! assert_eq!(far, Some(623540829615589));
+//! ```
+
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Rows of differences of `samples`, down to the first constant one.
+///
+/// A single value counts as a constant row, so only empty `samples` give
+/// `None`.
+fn difference_table(samples: &[i64]) -> Option<Vec<Vec<i128>>> {
+    let mut rows = vec![samples.iter().map(|&s| s.into()).collect::<Vec<i128>>()];
+    loop {
+        let last = rows.last().expect("starts with a row");
+        if last.is_empty() {
+            return None;
+        }
+        if last.windows(2).all(|w| w[0] == w[1]) {
+            return Some(rows);
+        }
+        let next = last.windows(2).map(|w| w[1] - w[0]).collect();
+        rows.push(next);
+    }
+}
+
+/// Degree of the polynomial generating `samples`, found by taking differences
+/// until they're constant.
+///
+/// `n` samples always fit a polynomial of degree `n - 1`, so this is only
+/// `None` for no samples at all.
+pub fn degree(samples: &[i64]) -> Option<usize> {
+    difference_table(samples).map(|rows| rows.len() - 1)
+}
+
+/// The value at `index` of the polynomial sequence starting with `samples`.
+///
+/// `index` 0 is the first sample, negative indices go backwards. `None` if
+/// there are no samples or the value doesn't fit in an `i128`.
+pub fn extrapolate(samples: &[i64], index: i64) -> Option<i128> {
+    let rows = difference_table(samples)?;
+    // Newton's forward formula, f(k) = sum of binomial(k, j) * (j-th difference)
+    let k = i128::from(index);
+    let mut binomial = 1i128;
+    let mut value = 0i128;
+    for (j, row) in (0i128..).zip(&rows) {
+        if j > 0 {
+            // binomial(k, j) = binomial(k, j - 1) * (k - j + 1) / j, exactly
+            binomial = binomial.checked_mul(k - j + 1)? / j;
+        }
+        value = value.checked_add(binomial.checked_mul(row[0])?)?;
+    }
+    Some(value)
+}
+
+/// The value right after `samples`
+pub fn next(samples: &[i64]) -> Option<i128> {
+    extrapolate(samples, samples.len().try_into().ok()?)
+}
+
+/// The value right before `samples`
+pub fn previous(samples: &[i64]) -> Option<i128> {
+    extrapolate(samples, -1)
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// An exact fraction, always in lowest terms with a positive denominator.
+///
+/// The operators panic on overflow, the `checked_` methods don't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// `numerator / denominator`, panics if `denominator` is 0
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert_ne!(denominator, 0, "denominator of 0");
+        let g = gcd(numerator, denominator) * denominator.signum();
+        Self {
+            numerator: numerator / g,
+            denominator: denominator / g,
+        }
+    }
+
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    /// The value if it's a whole number
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let g = gcd(self.denominator, other.denominator);
+        let (a, b) = (self.denominator / g, other.denominator / g);
+        let numerator = self
+            .numerator
+            .checked_mul(b)?
+            .checked_add(other.numerator.checked_mul(a)?)?;
+        Some(Self::new(numerator, self.denominator.checked_mul(b)?))
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // cancel crosswise first to keep the numbers small
+        let g1 = gcd(self.numerator, other.denominator).max(1);
+        let g2 = gcd(other.numerator, self.denominator).max(1);
+        Some(Self::new(
+            (self.numerator / g1).checked_mul(other.numerator / g2)?,
+            (self.denominator / g2).checked_mul(other.denominator / g1)?,
+        ))
+    }
+
+    /// `None` on overflow or division by zero
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.numerator == 0 {
+            return None;
+        }
+        self.checked_mul(Self::new(other.denominator, other.numerator))
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Self::new(n, 1)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self::new(n.into(), 1)
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).expect("rational overflow")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.numerator, self.denominator)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other).expect("rational overflow")
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        assert_ne!(other.numerator, 0, "division by zero");
+        self.checked_div(other).expect("rational overflow")
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            d => write!(f, "{}/{d}", self.numerator),
+        }
+    }
+}
+
+/// A polynomial with rational coefficients
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polynomial {
+    /// Lowest power first, without trailing zeroes
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    /// `coefficients[i] * x^i` summed up
+    pub fn new(mut coefficients: Vec<Rational>) -> Self {
+        while coefficients.last() == Some(&Rational::ZERO) {
+            coefficients.pop();
+        }
+        Self { coefficients }
+    }
+
+    /// The lowest degree polynomial through all `points`, by Lagrange
+    /// interpolation.
+    ///
+    /// Panics if two points share an `x`.
+    pub fn interpolate(points: &[(i64, i64)]) -> Self {
+        let mut coefficients = vec![Rational::ZERO; points.len()];
+        for (i, &(xi, yi)) in points.iter().enumerate() {
+            // yi * product of (x - xj) / (xi - xj)
+            let mut basis = vec![Rational::from(yi)];
+            for (j, &(xj, _)) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                assert_ne!(xi, xj, "two points at x = {xi}");
+                // i64 differences can overflow, everything after is i128
+                let scale = Rational::ONE / Rational::from(i128::from(xi) - i128::from(xj));
+                let mut next = vec![Rational::ZERO; basis.len() + 1];
+                for (power, &c) in basis.iter().enumerate() {
+                    next[power + 1] = next[power + 1] + c * scale;
+                    next[power] = next[power] - c * scale * Rational::from(xj);
+                }
+                basis = next;
+            }
+            for (total, c) in coefficients.iter_mut().zip(basis) {
+                *total = *total + c;
+            }
+        }
+        Self::new(coefficients)
+    }
+
+    /// Lowest power first
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /// `None` for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// The value at `x`, `None` if it overflows
+    pub fn eval(&self, x: i64) -> Option<Rational> {
+        let x = Rational::from(x);
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(Rational::ZERO, |acc, &c| acc.checked_mul(x)?.checked_add(c))
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.coefficients.is_empty() {
+            return write!(f, "0");
+        }
+        let terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| **c != Rational::ZERO);
+        for (n, (power, &c)) in terms.enumerate() {
+            let c = match n {
+                0 => c,
+                _ if c.numerator < 0 => {
+                    write!(f, " - ")?;
+                    -c
+                }
+                _ => {
+                    write!(f, " + ")?;
+                    c
+                }
+            };
+            match power {
+                0 => write!(f, "{c}")?,
+                1 => write!(f, "{c}x")?,
+                _ => write!(f, "{c}x^{power}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_extrapolate() {
+    assert_eq!(degree(&[0, 3, 6, 9, 12, 15]), Some(1));
+    assert_eq!(next(&[0, 3, 6, 9, 12, 15]), Some(18));
+    assert_eq!(previous(&[0, 3, 6, 9, 12, 15]), Some(-3));
+    assert_eq!(next(&[1, 3, 6, 10, 15, 21]), Some(28));
+    assert_eq!(previous(&[1, 3, 6, 10, 15, 21]), Some(0));
+    assert_eq!(degree(&[7, 7]), Some(0));
+    assert_eq!(degree(&[1, 2, 4]), Some(2));
+    assert_eq!(next(&[1, 2, 4]), Some(7));
+    assert_eq!(degree(&[5]), Some(0));
+    assert_eq!(previous(&[5]), Some(5));
+    assert_eq!(degree(&[]), None);
+    assert_eq!(next(&[]), None);
+
+    // squares, far out and far back
+    let squares: Vec<i64> = (0..5).map(|n| n * n).collect();
+    assert_eq!(
+        extrapolate(&squares, 3_000_000_000),
+        Some(9 * 10i128.pow(18))
+    );
+    assert_eq!(extrapolate(&squares, -7), Some(49));
+    assert_eq!(
+        extrapolate(&squares, i64::MAX),
+        Some(i64::MAX as i128 * i64::MAX as i128)
+    );
+    let cubes: Vec<i64> = (0..5).map(|n| n * n * n).collect();
+    assert_eq!(extrapolate(&cubes, i64::MAX), None);
+}
+
+#[test]
+fn test_polynomial() {
+    let half = Rational::new(1, 2);
+    assert_eq!(Rational::new(2, -4), -half);
+    assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+    assert_eq!((half * Rational::from(4i64)).to_integer(), Some(2));
+    assert_eq!(half.checked_div(Rational::ZERO), None);
+    assert_eq!(Rational::new(-3, 6).to_string(), "-1/2");
+
+    // triangular numbers, n (n + 1) / 2
+    let triangle = Polynomial::interpolate(&[(1, 1), (2, 3), (3, 6)]);
+    assert_eq!(triangle.coefficients(), [Rational::ZERO, half, half]);
+    assert_eq!(triangle.to_string(), "1/2x^2 + 1/2x");
+    assert_eq!(triangle.eval(100), Some(Rational::from(5050i64)));
+    assert_eq!(Polynomial::interpolate(&[(0, 4), (5, 4)]).degree(), Some(0));
+    assert_eq!(Polynomial::interpolate(&[(3, 0)]).degree(), None);
+    assert_eq!(Polynomial::new(vec![]).eval(3), Some(Rational::ZERO));
+
+    // x^2 - 3x + 1
+    let negative = Polynomial::interpolate(&[(0, 1), (1, -1), (2, -1)]);
+    assert_eq!(negative.to_string(), "1x^2 - 3x + 1");
+    assert_eq!((-half).to_string(), "-1/2");
+    let leading = Polynomial::new(vec![-half, Rational::ZERO, -Rational::ONE]);
+    assert_eq!(leading.to_string(), "-1x^2 - 1/2");
+
+    // far apart points whose x difference overflows an i64
+    let far = Polynomial::interpolate(&[(i64::MAX, 0), (-1, 0)]);
+    assert_eq!(far.degree(), None);
+    let line = Polynomial::interpolate(&[(i64::MAX, i64::MAX), (i64::MIN, i64::MIN)]);
+    assert_eq!(line.eval(5), Some(Rational::from(5i64)));
+}
@@ -0,0 +1,172 @@
+//! Reading the block letters some puzzles draw their answer with.
+//!
+//! Both fonts used on adventofcode.com are supported, the 4x6 one (e.g.
+//! 2016 day 8, 2022 day 10) and the 6x10 one (2018 day 10). The font is picked
+//! from the height of the drawing.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let screen = "
+//! █..█.███.
+//! █..█..█..
+//! ████..█..
+//! █..█..█..
+//! █..█..█..
+//! █..█.███.";
+//! assert_eq!(aoc::ocr::recognize(screen)?, "HI");
+//! # Ok::<(), eyre::Report>(())
+//! ```
+
+use eyre::Report;
+
+/// Letters of the 4x6 font
+const SMALL: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters of the 6x10 font
+#[rustfmt::skip]
+const LARGE: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Recognize the letters drawn in `s`.
+///
+/// `#` and `█` are lit pixels, anything else is off. Blank lines and columns
+/// around the letters are ignored.
+pub fn recognize(s: &str) -> Result<String, Report> {
+    let rows: Vec<Vec<bool>> = s
+        .lines()
+        .map(|line| line.chars().map(|c| matches!(c, '#' | '█')).collect())
+        .collect();
+    recognize_rows(&rows)
+}
+
+/// Recognize the letters drawn in `rows` of pixels, `true` being lit.
+pub fn recognize_rows<R: AsRef<[bool]>>(rows: &[R]) -> Result<String, Report> {
+    let rows: Vec<&[bool]> = rows
+        .iter()
+        .map(AsRef::as_ref)
+        .skip_while(|row| !row.contains(&true))
+        .collect();
+    let height = rows
+        .iter()
+        .rposition(|row| row.contains(&true))
+        .map_or(0, |last| last + 1);
+    let rows = &rows[..height];
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if (0..height).all(|y| !lit(x, y)) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && (0..height).any(|y| lit(x, y)) {
+            x += 1;
+        }
+        let glyph: Vec<String> = (0..height)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        text.push(letter(&glyph).ok_or_else(|| {
+            eyre::eyre!("unknown letter at column {start}:\n{}", glyph.join("\n"))
+        })?);
+    }
+    if text.is_empty() {
+        eyre::bail!("no letters found");
+    }
+    Ok(text)
+}
+
+/// Find the letter drawn by `glyph`, trimmed to its lit columns
+fn letter(glyph: &[String]) -> Option<char> {
+    let matches = |rows: &[&str]| rows.iter().eq(glyph.iter());
+    match glyph.len() {
+        6 => SMALL
+            .iter()
+            .find(|(_, rows)| matches(rows))
+            .map(|(c, _)| *c),
+        10 => LARGE
+            .iter()
+            .find(|(_, rows)| matches(rows))
+            .map(|(c, _)| *c),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_recognize_small() {
+    let mut rows = vec![String::new(); 6];
+    for (_, glyph) in SMALL {
+        for (row, line) in rows.iter_mut().zip(glyph) {
+            row.push_str(line);
+            row.push('.');
+        }
+    }
+    let letters: String = SMALL.iter().map(|(c, _)| c).collect();
+    assert_eq!(recognize(&rows.join("\n")).unwrap(), letters);
+
+    let screen = "
+███..█....█..█.████..██..███..████.████.
+█..█.█....█..█.█....█..█.█..█....█.█....
+█..█.█....████.███..█....█..█...█..███..
+███..█....█..█.█....█.██.███...█...█....
+█....█....█..█.█....█..█.█....█....█....
+█....████.█..█.████..███.█....████.█....
+";
+    assert_eq!(recognize(screen).unwrap(), "PLHEGPZF");
+    assert!(recognize("#\n#").is_err());
+    assert!(recognize("...").is_err());
+}
+
+#[test]
+fn test_recognize_large() {
+    let mut rows = vec![vec![]; 12];
+    for (_, glyph) in LARGE {
+        for (row, line) in rows[1..].iter_mut().zip(glyph) {
+            row.extend(line.chars().map(|c| c == '#'));
+            row.extend([false, false]);
+        }
+    }
+    let letters: String = LARGE.iter().map(|(c, _)| c).collect();
+    assert_eq!(recognize_rows(&rows).unwrap(), letters);
+}
@@ -0,0 +1,335 @@
+//! Runtime registry of every [`Solver`] implementation.
+//!
+//! Each day crate registers its solvers with [`register!`](crate::register),
+//! after which they can be looked up by `(year, day, part)` without knowing
+//! the concrete `Solution` type.
+//!
+//! Days implementing [`DaySolver`] can instead use
+//! [`register_day!`](crate::register_day), which also registers a
+//! [`DayEntry`] that solves both parts from a single parse.
+//!
+//! Registration happens at link time, so only crates that are actually linked
+//! into the final binary show up in [`solvers`].
+
+use std::time::Instant;
+
+use eyre::{Report, WrapErr};
+
+use crate::{
+    alloc::{self, Allocations},
+    DaySolver, Solver, Timings,
+};
+
+/// A type-erased [`Solver`] for a single part of a day.
+#[derive(Debug)]
+pub struct SolverEntry {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    /// Module path of the registering crate, e.g.
+    /// `y2023_day8_haunted_wasteland`
+    pub krate: &'static str,
+    run: fn(&str) -> Result<Run, Report>,
+}
+
+/// The answer of a solver, and how long it took to get it
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub answer: String,
+    pub timings: Timings,
+    /// Only measured when a
+    /// [`CountingAllocator`](crate::alloc::CountingAllocator) is installed
+    pub allocations: Option<Allocations>,
+}
+
+inventory::collect!(SolverEntry);
+
+/// A type-erased [`DaySolver`], solving both parts of a day at once.
+#[derive(Debug)]
+pub struct DayEntry {
+    pub year: u32,
+    pub day: u32,
+    /// Module path of the registering crate
+    pub krate: &'static str,
+    run: fn(&str) -> [Result<Run, Report>; 2],
+}
+
+inventory::collect!(DayEntry);
+
+impl SolverEntry {
+    #[doc(hidden)]
+    pub const fn new<
+        S: Solver<YEAR, DAY, PART>,
+        const YEAR: u32,
+        const DAY: u32,
+        const PART: u32,
+    >(
+        krate: &'static str,
+    ) -> Self {
+        Self {
+            year: YEAR,
+            day: DAY,
+            part: PART,
+            krate,
+            run: run_erased::<S, YEAR, DAY, PART>,
+        }
+    }
+
+    /// Parse `input` and solve it, returning the answer as a string.
+    pub fn solve(&self, input: &str) -> Result<String, Report> {
+        self.run(input).map(|run| run.answer)
+    }
+
+    /// Parse `input` and solve it, timing both steps.
+    pub fn run(&self, input: &str) -> Result<Run, Report> {
+        (self.run)(input).wrap_err_with(|| {
+            eyre::eyre!("could not solve {}-{}-{}", self.year, self.day, self.part)
+        })
+    }
+}
+
+fn run_erased<S: Solver<YEAR, DAY, PART>, const YEAR: u32, const DAY: u32, const PART: u32>(
+    input: &str,
+) -> Result<Run, Report> {
+    std::panic::catch_unwind(|| {
+        let start = Instant::now();
+        let (parsed, parse_allocations) = alloc::measure(|| S::generate_input(input));
+        let parse = start.elapsed();
+        let parsed = parsed?;
+
+        let start = Instant::now();
+        let (output, solve_allocations) = alloc::measure(|| S::solve(&parsed));
+        let solve = start.elapsed();
+
+        Ok(Run {
+            answer: output?.to_string(),
+            timings: Timings { parse, solve },
+            allocations: parse_allocations
+                .zip(solve_allocations)
+                .map(|(parse, solve)| Allocations { parse, solve }),
+        })
+    })
+    .map_err(|_| eyre::eyre!("solver panicked"))?
+}
+
+impl DayEntry {
+    #[doc(hidden)]
+    pub const fn new<S: DaySolver<YEAR, DAY>, const YEAR: u32, const DAY: u32>(
+        krate: &'static str,
+    ) -> Self {
+        Self {
+            year: YEAR,
+            day: DAY,
+            krate,
+            run: run_day_erased::<S, YEAR, DAY>,
+        }
+    }
+
+    /// Parse `input` once and solve both parts.
+    ///
+    /// The parse is only counted in the timings of part 1.
+    pub fn run(&self, input: &str) -> [Result<Run, Report>; 2] {
+        (self.run)(input).map(|run| {
+            run.wrap_err_with(|| eyre::eyre!("could not solve {}-{}", self.year, self.day))
+        })
+    }
+}
+
+fn run_day_erased<S: DaySolver<YEAR, DAY>, const YEAR: u32, const DAY: u32>(
+    input: &str,
+) -> [Result<Run, Report>; 2] {
+    let result = std::panic::catch_unwind(|| {
+        let start = Instant::now();
+        let (parsed, parse_allocations) = alloc::measure(|| S::generate_input(input));
+        let parse = start.elapsed();
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                let second = eyre::eyre!("{e:#}");
+                return [Err(e), Err(second)];
+            }
+        };
+
+        let start = Instant::now();
+        let (first, first_allocations) = alloc::measure(|| S::solve_first(&parsed));
+        let first_solve = start.elapsed();
+        let (answer, state) = match first {
+            Ok(first) => first,
+            Err(e) => {
+                let second = eyre::eyre!("part 1 failed: {e:#}");
+                return [Err(e), Err(second)];
+            }
+        };
+        let first = Run {
+            answer: answer.to_string(),
+            timings: Timings {
+                parse,
+                solve: first_solve,
+            },
+            allocations: parse_allocations
+                .zip(first_allocations)
+                .map(|(parse, solve)| Allocations { parse, solve }),
+        };
+
+        let start = Instant::now();
+        let (second, second_allocations) = alloc::measure(|| S::solve_second(&parsed, state));
+        let second_solve = start.elapsed();
+        let second = second.map(|answer| Run {
+            answer: answer.to_string(),
+            timings: Timings {
+                parse: Default::default(),
+                solve: second_solve,
+            },
+            allocations: second_allocations.map(|solve| Allocations {
+                parse: Default::default(),
+                solve,
+            }),
+        });
+        [Ok(first), second]
+    });
+    result.unwrap_or_else(|_| {
+        [
+            Err(eyre::eyre!("solver panicked")),
+            Err(eyre::eyre!("solver panicked")),
+        ]
+    })
+}
+
+/// All registered solvers, sorted by year, day and part.
+pub fn solvers() -> Vec<&'static SolverEntry> {
+    let mut solvers: Vec<_> = inventory::iter::<SolverEntry>.into_iter().collect();
+    solvers.sort_by_key(|s| (s.year, s.day, s.part));
+    solvers
+}
+
+/// Find the solver registered for the given `year`, `day` and `part`.
+pub fn get(year: u32, day: u32, part: u32) -> Option<&'static SolverEntry> {
+    inventory::iter::<SolverEntry>
+        .into_iter()
+        .find(|s| (s.year, s.day, s.part) == (year, day, part))
+}
+
+/// Find the combined solver registered for the given `year` and `day`.
+pub fn get_day(year: u32, day: u32) -> Option<&'static DayEntry> {
+    inventory::iter::<DayEntry>
+        .into_iter()
+        .find(|d| (d.year, d.day) == (year, day))
+}
+
+/// Solve `input` with the solver registered for `year`, `day` and `part`.
+pub fn solve(year: u32, day: u32, part: u32, input: &str) -> Result<String, Report> {
+    get(year, day, part)
+        .ok_or_else(|| eyre::eyre!("no solver registered for {year}-{day}-{part}"))?
+        .solve(input)
+}
+
+/// Register a [`Solver`] implementation in the runtime registry.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// aoc::register!(Solution, Year2023, Day8, Part1);
+/// aoc::register!(Solution, Year2023, Day8, Part2);
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($solution:ty, $year:expr, $day:expr, $part:expr) => {
+        $crate::inventory::submit! {
+            $crate::registry::SolverEntry::new::<$solution, { $year }, { $day }, { $part }>(
+                module_path!(),
+            )
+        }
+    };
+}
+
+/// Register a [`DaySolver`] implementation in the runtime registry, both as
+/// a [`DayEntry`] and as a [`SolverEntry`] per part.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// aoc::register_day!(Solution, Year2022, Day7);
+/// ```
+#[macro_export]
+macro_rules! register_day {
+    ($solution:ty, $year:expr, $day:expr) => {
+        $crate::register!($solution, $year, $day, $crate::parts::Part1);
+        $crate::register!($solution, $year, $day, $crate::parts::Part2);
+        $crate::inventory::submit! {
+            $crate::registry::DayEntry::new::<$solution, { $year }, { $day }>(module_path!())
+        }
+    };
+}
+
+#[cfg(test)]
+struct TestSolution;
+
+#[cfg(test)]
+impl Solver<1, 1, 1> for TestSolution {
+    type Input<'a> = Vec<u32>;
+
+    type Output = u32;
+
+    fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
+        input
+            .split(',')
+            .map(|s| s.trim().parse().map_err(Into::into))
+            .collect()
+    }
+
+    fn solve(input: &Self::Input<'_>) -> Result<Self::Output, Report> {
+        Ok(input.iter().sum())
+    }
+}
+
+#[cfg(test)]
+register!(TestSolution, 1, 1, 1);
+
+#[cfg(test)]
+struct TestDay;
+
+#[cfg(test)]
+impl DaySolver<1, 2> for TestDay {
+    type Input<'a> = Vec<u32>;
+    type State = u32;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn generate_input(input: &'_ str) -> Result<Self::Input<'_>, Report> {
+        <TestSolution as Solver<1, 1, 1>>::generate_input(input)
+    }
+
+    fn solve_first(input: &Self::Input<'_>) -> Result<(u32, u32), Report> {
+        let sum = input.iter().sum();
+        Ok((sum, sum))
+    }
+
+    fn solve_second(input: &Self::Input<'_>, sum: u32) -> Result<u32, Report> {
+        Ok(sum * input.len() as u32)
+    }
+}
+
+#[cfg(test)]
+register_day!(TestDay, 1, 2);
+
+#[test]
+fn test_registry() {
+    assert_eq!(solve(1, 1, 1, "1, 2, 3").unwrap(), "6");
+    assert!(solve(1, 1, 1, "1, a").is_err());
+    assert!(solve(1, 1, 2, "1").is_err());
+    assert!(solvers()
+        .iter()
+        .any(|s| (s.year, s.day, s.part) == (1, 1, 1)));
+}
+
+#[test]
+fn test_day_registry() {
+    assert_eq!(solve(1, 2, 1, "1, 2, 3").unwrap(), "6");
+    assert_eq!(solve(1, 2, 2, "1, 2, 3").unwrap(), "18");
+    let [first, second] = get_day(1, 2).unwrap().run("1, 2, 3");
+    assert_eq!(first.unwrap().answer, "6");
+    assert_eq!(second.unwrap().answer, "18");
+    let [first, second] = get_day(1, 2).unwrap().run("1, a");
+    assert!(first.is_err() && second.is_err());
+    assert!(get_day(1, 1).is_none());
+}
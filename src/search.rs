@@ -0,0 +1,427 @@
+//! Searching state spaces that are generated on the fly.
+//!
+//! Implement [`SearchProblem`] for the puzzle, then pick a driver:
+//!
+//! * [`astar`], optimal with an admissible heuristic, Dijkstra without one
+//! * [`ida_star`], optimal and uses little memory, for deep but narrow searches
+//! * [`branch_and_bound`], a depth limited DFS that prunes anything that can't
+//!   beat the best solution so far
+//! * [`beam_search`], fast but not optimal, only keeps the best states of each
+//!   depth
+//!
+//! Every driver returns [`SearchStats`] next to the path, and logs them with
+//! [`tracing`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use aoc::search::{astar, SearchProblem};
+//!
+//! /// Reach `target` from 1 by doubling or adding one
+//! struct Reach {
+//!     target: u32,
+//! }
+//!
+//! impl SearchProblem for Reach {
+//!     type State = u32;
+//!     type Cost = u32;
+//!
+//!     fn successors(&self, &n: &u32) -> impl Iterator<Item = (u32, u32)> {
+//!         [(n * 2, 1), (n + 1, 1)]
+//!             .into_iter()
+//!             .filter(|&(n, _)| n <= self.target)
+//!     }
+//!
+//!     fn is_goal(&self, &n: &u32) -> bool {
+//!         n == self.target
+//!     }
+//! }
+//!
+//! let (path, stats) = astar(&Reach { target: 10 }, 1);
+//! let path = path.unwrap();
+//! assert_eq!(path.cost, 4);
+//! assert_eq!(path.states, [1, 2, 4, 5, 10]);
+//! assert!(stats.expanded >= 4);
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+    ops::Add,
+};
+
+/// A puzzle whose states and moves are generated while searching
+pub trait SearchProblem {
+    type State: Clone + Eq + Hash;
+    type Cost: Copy + Ord + Add<Output = Self::Cost> + Default + Debug;
+
+    /// The states reachable in one move from `state`, with the cost of the move
+    fn successors(&self, state: &Self::State) -> impl Iterator<Item = (Self::State, Self::Cost)>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// Estimate of the cost left to a goal.
+    ///
+    /// Must never overestimate for [`astar`] and [`ida_star`] to find the
+    /// cheapest path. Defaults to no estimate at all.
+    fn heuristic(&self, _state: &Self::State) -> Self::Cost {
+        Self::Cost::default()
+    }
+}
+
+/// A path found by a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    /// Every state from the start to the goal, both included
+    pub states: Vec<S>,
+}
+
+/// What a search did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// States whose successors were generated
+    pub expanded: usize,
+    /// Successors generated
+    pub generated: usize,
+    /// Rounds of [`ida_star`] or depths of [`beam_search`], 1 for the others
+    pub iterations: usize,
+}
+
+/// The cheapest path found, if any, and what it took to find it
+pub type SearchResult<P> = (
+    Option<Path<<P as SearchProblem>::State, <P as SearchProblem>::Cost>>,
+    SearchStats,
+);
+
+/// A state on the path of [`ida_star`] or [`branch_and_bound`], with its cost
+/// and the successors still to visit
+type Frame<P> = (
+    <P as SearchProblem>::State,
+    <P as SearchProblem>::Cost,
+    std::vec::IntoIter<(<P as SearchProblem>::State, <P as SearchProblem>::Cost)>,
+);
+
+/// States found so far, with how they were reached
+struct Arena<P: SearchProblem> {
+    /// State, cost to reach it and the index of the previous state
+    nodes: Vec<(P::State, P::Cost, Option<usize>)>,
+    index: HashMap<P::State, usize>,
+}
+
+impl<P: SearchProblem> Arena<P> {
+    fn new(start: P::State) -> Self {
+        Self {
+            nodes: vec![(start.clone(), P::Cost::default(), None)],
+            index: HashMap::from([(start, 0)]),
+        }
+    }
+
+    /// Record reaching `state` for `cost`, returns its index if that's the
+    /// cheapest way to it so far.
+    fn reach(&mut self, state: P::State, cost: P::Cost, previous: usize) -> Option<usize> {
+        match self.index.entry(state) {
+            Entry::Occupied(entry) => {
+                let node = &mut self.nodes[*entry.get()];
+                if cost >= node.1 {
+                    return None;
+                }
+                (node.1, node.2) = (cost, Some(previous));
+                Some(*entry.get())
+            }
+            Entry::Vacant(entry) => {
+                self.nodes.push((entry.key().clone(), cost, Some(previous)));
+                Some(*entry.insert(self.nodes.len() - 1))
+            }
+        }
+    }
+
+    fn path(&self, mut node: usize) -> Path<P::State, P::Cost> {
+        let cost = self.nodes[node].1;
+        let mut states = vec![self.nodes[node].0.clone()];
+        while let Some(previous) = self.nodes[node].2 {
+            states.push(self.nodes[previous].0.clone());
+            node = previous;
+        }
+        states.reverse();
+        Path { cost, states }
+    }
+}
+
+/// A* from `start` to the nearest goal
+pub fn astar<P: SearchProblem>(problem: &P, start: P::State) -> SearchResult<P> {
+    let mut arena = Arena::<P>::new(start.clone());
+    let mut heap = BinaryHeap::from([(
+        Reverse(problem.heuristic(&start)),
+        Reverse(P::Cost::default()),
+        0,
+    )]);
+    let mut stats = SearchStats {
+        iterations: 1,
+        ..SearchStats::default()
+    };
+
+    while let Some((_, Reverse(cost), node)) = heap.pop() {
+        if cost > arena.nodes[node].1 {
+            // reached cheaper since this was queued
+            continue;
+        }
+        let state = arena.nodes[node].0.clone();
+        if problem.is_goal(&state) {
+            let path = arena.path(node);
+            tracing::debug!(?stats, cost = ?path.cost, "A* found a path");
+            return (Some(path), stats);
+        }
+        stats.expanded += 1;
+        for (next, step) in problem.successors(&state) {
+            stats.generated += 1;
+            let cost = cost + step;
+            let estimate = cost + problem.heuristic(&next);
+            if let Some(next) = arena.reach(next, cost, node) {
+                heap.push((Reverse(estimate), Reverse(cost), next));
+            }
+        }
+    }
+    tracing::debug!(?stats, "A* found no path");
+    (None, stats)
+}
+
+/// Iterative deepening A* from `start` to the nearest goal.
+///
+/// Only remembers the current path, so states can be expanded many times.
+pub fn ida_star<P: SearchProblem>(problem: &P, start: P::State) -> SearchResult<P> {
+    let mut bound = problem.heuristic(&start);
+    let mut stats = SearchStats::default();
+    loop {
+        stats.iterations += 1;
+        // smallest estimate over the bound, the bound of the next round
+        let mut next_bound: Option<P::Cost> = None;
+        // the current path, depth first without recursing
+        let mut stack: Vec<Frame<P>> = vec![];
+        let mut on_path: HashSet<P::State> = HashSet::new();
+        let mut next = Some((start.clone(), P::Cost::default()));
+
+        loop {
+            if let Some((state, cost)) = next.take() {
+                let estimate = cost + problem.heuristic(&state);
+                if on_path.contains(&state) {
+                    // going in circles
+                } else if estimate > bound {
+                    next_bound = Some(next_bound.map_or(estimate, |b| b.min(estimate)));
+                } else if problem.is_goal(&state) {
+                    tracing::debug!(?stats, ?cost, "IDA* found a path");
+                    let states = stack.into_iter().map(|(s, ..)| s).chain([state]);
+                    let path = Path {
+                        cost,
+                        states: states.collect(),
+                    };
+                    return (Some(path), stats);
+                } else {
+                    stats.expanded += 1;
+                    let successors: Vec<_> = problem.successors(&state).collect();
+                    stats.generated += successors.len();
+                    on_path.insert(state.clone());
+                    stack.push((state, cost, successors.into_iter()));
+                }
+            }
+            let Some((_, cost, successors)) = stack.last_mut() else {
+                break;
+            };
+            match successors.next() {
+                Some((state, step)) => next = Some((state, *cost + step)),
+                None => {
+                    let (state, ..) = stack.pop().expect("stack isn't empty");
+                    on_path.remove(&state);
+                }
+            }
+        }
+
+        match next_bound {
+            Some(next) => bound = next,
+            None => {
+                tracing::debug!(?stats, "IDA* found no path");
+                return (None, stats);
+            }
+        }
+    }
+}
+
+/// Depth first search up to `max_depth` moves, returning the cheapest goal.
+///
+/// Branches that can't beat the best path so far, using the heuristic, are
+/// pruned, as are states already reached for less.
+pub fn branch_and_bound<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    max_depth: usize,
+) -> SearchResult<P> {
+    let mut stats = SearchStats {
+        iterations: 1,
+        ..SearchStats::default()
+    };
+    let mut best: Option<Path<P::State, P::Cost>> = None;
+    let mut seen: HashMap<P::State, (P::Cost, usize)> = HashMap::new();
+    // the current path, depth first without recursing
+    let mut stack: Vec<Frame<P>> = vec![];
+    let mut next = Some((start, P::Cost::default()));
+
+    loop {
+        if let Some((state, cost)) = next.take() {
+            let depth = stack.len();
+            let hopeless = best
+                .as_ref()
+                .is_some_and(|best| cost + problem.heuristic(&state) >= best.cost);
+            // reached before for less, and with at least as many moves left
+            let beaten = seen
+                .get(&state)
+                .is_some_and(|&(c, d)| c <= cost && d <= depth);
+            if !hopeless && !beaten {
+                seen.insert(state.clone(), (cost, depth));
+                if problem.is_goal(&state) {
+                    let states = stack.iter().map(|(s, ..)| s.clone()).chain([state]);
+                    best = Some(Path {
+                        cost,
+                        states: states.collect(),
+                    });
+                } else if depth < max_depth {
+                    stats.expanded += 1;
+                    let successors: Vec<_> = problem.successors(&state).collect();
+                    stats.generated += successors.len();
+                    stack.push((state, cost, successors.into_iter()));
+                }
+            }
+        }
+        let Some((_, cost, successors)) = stack.last_mut() else {
+            break;
+        };
+        match successors.next() {
+            Some((state, step)) => next = Some((state, *cost + step)),
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    tracing::debug!(?stats, cost = ?best.as_ref().map(|b| b.cost), "branch and bound done");
+    (best, stats)
+}
+
+/// Breadth first, keeping only the `width` states with the lowest cost plus
+/// heuristic at every depth.
+///
+/// Returns the cheapest goal of the first depth that reaches one, which isn't
+/// necessarily the cheapest path overall.
+pub fn beam_search<P: SearchProblem>(
+    problem: &P,
+    start: P::State,
+    width: usize,
+) -> SearchResult<P> {
+    let mut arena = Arena::<P>::new(start);
+    let mut seen = HashSet::from([0]);
+    let mut beam = vec![0];
+    let mut stats = SearchStats::default();
+
+    while !beam.is_empty() {
+        if let Some(&goal) = beam
+            .iter()
+            .filter(|&&n| problem.is_goal(&arena.nodes[n].0))
+            .min_by_key(|&&n| arena.nodes[n].1)
+        {
+            let path = arena.path(goal);
+            tracing::debug!(?stats, cost = ?path.cost, "beam search found a path");
+            return (Some(path), stats);
+        }
+        let mut next_beam = vec![];
+        for node in beam {
+            stats.expanded += 1;
+            let (state, cost) = (arena.nodes[node].0.clone(), arena.nodes[node].1);
+            for (next, step) in problem.successors(&state) {
+                stats.generated += 1;
+                if let Some(next) = arena.reach(next, cost + step, node) {
+                    if seen.insert(next) {
+                        next_beam.push(next);
+                    }
+                }
+            }
+        }
+        next_beam.sort_by_cached_key(|&n| arena.nodes[n].1 + problem.heuristic(&arena.nodes[n].0));
+        next_beam.truncate(width);
+        beam = next_beam;
+        stats.iterations += 1;
+    }
+    tracing::debug!(?stats, "beam search found no path");
+    (None, stats)
+}
+
+/// Walk from the top left to the bottom right of a grid of digits, paying the
+/// digit of every cell entered
+#[cfg(test)]
+struct Walk(crate::Grid<u32>);
+
+#[cfg(test)]
+impl SearchProblem for Walk {
+    type State = (usize, usize);
+    type Cost = u32;
+
+    fn successors(&self, &(x, y): &(usize, usize)) -> impl Iterator<Item = ((usize, usize), u32)> {
+        self.0.neighbours4(x, y).map(|p| (p, self.0[p]))
+    }
+
+    fn is_goal(&self, &(x, y): &(usize, usize)) -> bool {
+        (x + 1, y + 1) == (self.0.width(), self.0.height())
+    }
+
+    fn heuristic(&self, &(x, y): &(usize, usize)) -> u32 {
+        (self.0.width() - 1 - x + self.0.height() - 1 - y) as u32
+    }
+}
+
+#[test]
+fn test_search() {
+    // 2021 day 15 example
+    let grid = crate::Grid::parse(
+        "1163751742\n\
+         1381373672\n\
+         2136511328\n\
+         3694931569\n\
+         7463417111\n\
+         1319128137\n\
+         1359912421\n\
+         3125421639\n\
+         1293138521\n\
+         2311944581",
+        |c| c.to_digit(10).ok_or_else(|| eyre::eyre!("not a digit")),
+    )
+    .unwrap();
+    let walk = Walk(grid);
+
+    let (path, stats) = astar(&walk, (0, 0));
+    let path = path.unwrap();
+    assert!(stats.expanded > 0 && stats.generated >= stats.expanded);
+    assert_eq!(path.cost, 40);
+    assert_eq!(path.states.first(), Some(&(0, 0)));
+    assert_eq!(path.states.last(), Some(&(9, 9)));
+    assert_eq!(path.states[1..].iter().map(|&p| walk.0[p]).sum::<u32>(), 40);
+
+    let (path, stats) = ida_star(&walk, (0, 0));
+    assert_eq!(path.unwrap().cost, 40);
+    assert!(stats.iterations > 1);
+    let (path, stats) = branch_and_bound(&walk, (0, 0), 30);
+    assert_eq!(path.unwrap().cost, 40);
+    assert_eq!(stats.iterations, 1);
+    assert_eq!(branch_and_bound(&walk, (0, 0), 10).0, None);
+    let (beam, stats) = beam_search(&walk, (0, 0), 100);
+    let beam = beam.unwrap();
+    assert_eq!(stats.iterations, 18);
+    assert!(beam.cost >= 40);
+    assert_eq!(beam.states.len(), 19);
+
+    // far deeper than the call stack would allow recursing
+    let line = Walk(crate::Grid::from_elem(200_000, 1, 1));
+    let (path, _) = branch_and_bound(&line, (0, 0), usize::MAX);
+    assert_eq!(path.map(|p| p.cost), Some(199_999));
+    let (path, stats) = ida_star(&line, (0, 0));
+    assert_eq!(path.map(|p| p.states.len()), Some(200_000));
+    assert_eq!(stats.iterations, 1);
+}
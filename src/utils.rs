@@ -1,153 +1,250 @@
-use itertools::Itertools;
-
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    iter::Sum,
+    ops::{Add, Sub},
+};
+
+use eyre::Report;
+
+pub mod bitset;
+pub mod disjoint;
+pub mod grid;
+pub mod interval;
+pub mod offset_map;
+pub mod point;
+pub mod window;
+
+pub use bitset::BitSet;
+#[allow(deprecated)]
+pub use disjoint::{ArrayArrayTools, DisjointMut};
+pub use grid::Grid;
+pub use interval::IntervalSet;
+pub use offset_map::OffsetMap;
+pub use point::{Dir4, Dir8, GridIndex, Point2, Point3};
+pub use window::{rolling_distinct_windows, RollingWindows};
+
+/// Get multiple mutable references to elements in an array
+///
+/// Defined here rather than next to [`DisjointMut`], whose `disjoint_mut` it
+/// would clash with. Having both traits in scope makes calls ambiguous, so
+/// import only one of them.
+///
+/// # Examples
+///
+/// ```rust
+/// #![allow(deprecated)]
+/// use aoc::utils::ArrayTools;
+///
+/// let mut v = vec![1, 2, 3];
+/// let [a, b] = v.disjoint_mut([0, 2])?;
+/// std::mem::swap(a, b);
+/// assert_eq!(v, [3, 2, 1]);
+/// assert!(v.disjoint_mut([1, 1]).is_err());
+/// assert!(v.disjoint_mut([3]).is_err());
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[deprecated(note = "use `DisjointMut` instead")]
 pub trait ArrayTools<T> {
+    #[deprecated(note = "use `DisjointMut::disjoint_mut` instead")]
     fn disjoint_mut<const N: usize>(
         &mut self,
         indices: [usize; N],
     ) -> Result<[&mut T; N], eyre::Report>;
 }
 
+#[allow(deprecated)]
 impl<T> ArrayTools<T> for [T] {
-    /// Get multiple mutable references to elements in an array
     fn disjoint_mut<const N: usize>(
         &mut self,
         indices: [usize; N],
     ) -> Result<[&mut T; N], eyre::Report> {
-        self.get_many_mut(indices).map_err(Into::into)
+        <Self as DisjointMut<usize>>::disjoint_mut(self, indices)
     }
 }
 
-/// Takes indices into e.g a `Vec<Vec<T>>` and returns them mutably
-pub trait ArrayArrayTools<C, T> {
-    fn double_disjoint_mut<const N: usize>(
-        &mut self,
-        indices: [(usize, usize); N],
-    ) -> Result<[&mut T; N], eyre::Report>;
-}
-
-impl<C, T> ArrayArrayTools<C, T> for [C]
-where
-    C: AsMut<[T]> + AsRef<[T]>,
-{
-    fn double_disjoint_mut<const N: usize>(
-        &mut self,
-        indices: [(usize, usize); N],
-    ) -> Result<[&mut T; N], eyre::Report> {
-        fn get_many_check_valid(indices: impl Iterator<Item = usize> + Clone, len: usize) -> bool {
-            let mut valid = true;
-            for (i, idx) in indices.clone().enumerate() {
-                valid &= idx < len;
-                for idx2 in indices.clone().take(i) {
-                    valid &= idx != idx2;
-                }
-            }
-            valid
-        }
-
-        let mut valid = true;
-        for &(i, j) in indices.iter().unique_by(|(i, _)| i) {
-            let Some(len) = self.get(i).map(|s| s.as_ref().len()) else {
-                eyre::bail!("index {i}, {j} out of bound")
-            };
-            valid &= get_many_check_valid(
-                indices
-                    .iter()
-                    .filter(|(l, _)| i == *l)
-                    .map(|(_, k)| k)
-                    .copied(),
-                len,
-            );
-        }
-        if !valid {
-            eyre::bail!("invalid index found")
-        }
-
-        let mut arr: std::mem::MaybeUninit<[&mut T; N]> = std::mem::MaybeUninit::uninit();
-
-        // SAFETY: We expect `indices` to contain disjunct values that are
-        // in bounds of all slices in self.
-        let arr_ptr = arr.as_mut_ptr();
-        unsafe {
-            for i in 0..N {
-                let idx = *indices.get_unchecked(i);
+#[test]
+#[allow(deprecated)]
+fn test_double_disjoint_mut() {
+    let mut v: Vec<Vec<u8>> = vec![vec![1, 2, 3, 4], vec![4, 3, 2, 1]];
+    assert!(v.double_disjoint_mut([(0, 0), (0, 1)]).is_ok());
+    let [a, b] = v.double_disjoint_mut([(0, 0), (1, 0)]).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(v, vec![vec![4, 2, 3, 4], vec![1, 3, 2, 1]]);
 
-                let slice: *mut [T] = self[idx.0].as_mut();
+    let mut v: Vec<Vec<u8>> = vec![vec![1, 2, 3, 4], vec![4, 3, 2, 1]];
+    assert!(v.double_disjoint_mut([(0, 4), (1, 0)]).is_err());
+    assert!(v.double_disjoint_mut([(0, 0), (4, 0)]).is_err());
+    v.double_disjoint_mut([(0, 0), (0, 3), (1, 0), (1, 1), (1, 2)])
+        .unwrap();
+    assert!(v.double_disjoint_mut([(0, 4), (0, 4)]).is_err());
 
-                *(*arr_ptr).get_unchecked_mut(i) = &mut *slice.get_unchecked_mut(idx.1);
-            }
-            Ok(arr.assume_init())
-        }
-    }
+    let [a, b] = v
+        .double_disjoint_mut([Point2::new(3, 0), Point2::new(0, 1)])
+        .unwrap();
+    assert_eq!((*a, *b), (4, 4));
 }
 
-// https://discord.com/channels/273534239310479360/386246790565068811/1049645867981881364
-///
+/// Adaptors puzzles keep needing, on top of [`Itertools`](itertools::Itertools)
 ///
 /// # Examples
 ///
-/// Get the position of the first distinct window of size 4.
-///
 /// ```rust
-/// use aoc::utils::rolling_distinct_windows;
-/// assert_eq!(rolling_distinct_windows(&vec![1,2,1,4,1,2,3], 4).position(|(_, c)| c == 4).unwrap(),3);
+/// use aoc::utils::IteratorExt;
+///
+/// let calories = [6000, 4000, 11000, 24000, 10000];
+/// assert_eq!(calories.iter().top_k(3), [&24000, &11000, &10000]);
+/// assert_eq!(calories.into_iter().min_max(), Some((4000, 24000)));
+///
+/// let depths = [199, 200, 208, 210, 200];
+/// let sums: Vec<_> = depths.into_iter().sum_windows(3).collect();
+/// assert_eq!(sums, [607, 618, 618]);
+///
+/// let numbers: u32 = "1 2 3".split(' ').map(str::parse::<u32>).try_sum()?;
+/// assert_eq!(numbers, 6);
+/// assert!([1, 2].iter().single().is_err());
+/// # Ok::<(), eyre::Report>(())
 /// ```
-pub fn rolling_distinct_windows(s: &[u8], n: usize) -> impl Iterator<Item = (&[u8], usize)> {
-    let mut in_window = [0; 256];
-    let mut count = 0;
-    (0..s.len()).filter_map(move |i| {
-        if i >= n {
-            in_window[s[i - n] as usize] -= 1;
-            count -= (in_window[s[i - n] as usize] == 0) as usize;
+pub trait IteratorExt: Iterator {
+    /// The `k` largest items, largest first
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord, {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            heap.push(Reverse(item));
+            if heap.len() > k {
+                heap.pop();
+            }
         }
-        in_window[s[i] as usize] += 1;
-        count += (in_window[s[i] as usize] == 1) as usize;
-        (i >= n - 1).then(|| (&s[i + 1 - n..i + 1], count))
-    })
-}
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
 
-pub trait RollingDistinctWindows {
-    type Ret<'a>
+    /// The `k` smallest items, smallest first
+    fn bottom_k(self, k: usize) -> Vec<Self::Item>
     where
-        Self: 'a;
+        Self: Sized,
+        Self::Item: Ord, {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            heap.push(item);
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        heap.into_sorted_vec()
+    }
 
-    fn rolling_distinct_windows(&self, n: usize) -> Self::Ret<'_>;
-}
+    /// How many times each index in `0..N` occurs, panics on indices past `N`
+    fn counts_into_array<const N: usize>(self) -> [usize; N]
+    where
+        Self: Sized,
+        Self::Item: Into<usize>, {
+        let mut counts = [0; N];
+        for index in self {
+            counts[index.into()] += 1;
+        }
+        counts
+    }
 
-impl RollingDistinctWindows for [u8] {
-    type Ret<'a> = impl Iterator<Item = (&'a [u8], usize)>;
-
-    fn rolling_distinct_windows(&self, n: usize) -> Self::Ret<'_> {
-        let s = self;
-        let mut in_window = [0; u8::MAX as usize + 1];
-        let mut count = 0;
-        (0..s.len()).filter_map(move |i| {
-            if i >= n {
-                in_window[s[i - n] as usize] -= 1;
-                count -= (in_window[s[i - n] as usize] == 0) as usize;
-            }
-            in_window[s[i] as usize] += 1;
-            count += (in_window[s[i] as usize] == 1) as usize;
-            (i >= n - 1).then(|| (&s[i + 1 - n..i + 1], count))
+    /// Sums of every `n` consecutive items, keeping a running total
+    fn sum_windows(self, n: usize) -> impl Iterator<Item = Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Copy + Add<Output = Self::Item> + Sub<Output = Self::Item>, {
+        assert!(n > 0, "windows of 0 items");
+        let mut window = VecDeque::with_capacity(n + 1);
+        let mut sum = None;
+        self.filter_map(move |item| {
+            window.push_back(item);
+            let total = match sum {
+                Some(total) => total + item,
+                None => item,
+            };
+            let total = if window.len() > n {
+                total - window.pop_front().expect("window is full")
+            } else {
+                total
+            };
+            sum = Some(total);
+            (window.len() == n).then_some(total)
         })
     }
-}
 
-#[test]
-fn test_double_disjoint_mut() {
-    let mut v: Vec<Vec<u8>> = vec![vec![1, 2, 3, 4], vec![4, 3, 2, 1]];
-    assert!(v.double_disjoint_mut([(0, 0), (0, 1)]).is_ok());
-    let [a, b] = v.double_disjoint_mut([(0, 0), (1, 0)]).unwrap();
-    std::mem::swap(a, b);
-    assert_eq!(v, vec![vec![4, 2, 3, 4], vec![1, 3, 2, 1]]);
+    /// Smallest and largest item, in one pass
+    fn min_max(self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self: Sized,
+        Self::Item: Ord + Clone, {
+        self.fold(None, |acc, item| match acc {
+            None => Some((item.clone(), item)),
+            Some((min, max)) => Some((min.min(item.clone()), max.max(item))),
+        })
+    }
 
-    let mut v: Vec<Vec<u8>> = vec![vec![1, 2, 3, 4], vec![4, 3, 2, 1]];
-    assert!(v.double_disjoint_mut([(0, 4), (1, 0)]).is_err());
-    assert!(v.double_disjoint_mut([(0, 0), (4, 0)]).is_err());
-    v.double_disjoint_mut([(0, 0), (0, 3), (1, 0), (1, 1), (1, 2)])
-        .unwrap();
-    assert!(v.double_disjoint_mut([(0, 4), (0, 4)]).is_err());
-}
+    /// Sum of the `Ok` values, or the first error
+    fn try_sum<T, E>(self) -> Result<T, Report>
+    where
+        Self: Sized + Iterator<Item = Result<T, E>>,
+        T: Sum,
+        E: Into<Report>, {
+        self.map(|item| item.map_err(Into::into)).sum()
+    }
 
-pub trait IteratorExt: Iterator {}
+    /// The only item, an error if there are none or more than one
+    fn single(mut self) -> Result<Self::Item, Report>
+    where
+        Self: Sized, {
+        let Some(item) = self.next() else {
+            eyre::bail!("expected exactly one item, found none");
+        };
+        if self.next().is_some() {
+            eyre::bail!("expected exactly one item, found more");
+        }
+        Ok(item)
+    }
+}
 
 impl<T: ?Sized> IteratorExt for T where T: Iterator {}
+
+#[test]
+fn test_iterator_ext() {
+    let numbers = [5, 1, 4, 1, 9, 2, 6];
+    assert_eq!(numbers.iter().top_k(3), [&9, &6, &5]);
+    assert_eq!(numbers.into_iter().bottom_k(3), [1, 1, 2]);
+    assert_eq!(numbers.into_iter().top_k(10).len(), 7);
+    assert!(numbers.into_iter().top_k(0).is_empty());
+
+    let counts: [usize; 10] = numbers.iter().map(|&n| n as u8).counts_into_array();
+    assert_eq!(counts, [0, 2, 1, 0, 1, 1, 1, 0, 0, 1]);
+
+    let sums: Vec<i32> = numbers.into_iter().sum_windows(3).collect();
+    assert_eq!(sums, [10, 6, 14, 12, 17]);
+    assert_eq!(numbers.into_iter().sum_windows(8).count(), 0);
+    assert_eq!(
+        numbers.into_iter().sum_windows(1).collect::<Vec<_>>(),
+        numbers
+    );
+
+    assert_eq!(numbers.into_iter().min_max(), Some((1, 9)));
+    assert_eq!(std::iter::empty::<u8>().min_max(), None);
+
+    let parsed: Result<u32, _> = ["1", "x", "3"].into_iter().map(str::parse::<u32>).try_sum();
+    assert!(parsed.is_err());
+    let reports = [Ok(1), Err(eyre::eyre!("nope"))];
+    assert_eq!(
+        reports
+            .into_iter()
+            .try_sum::<i32, _>()
+            .unwrap_err()
+            .to_string(),
+        "nope"
+    );
+
+    assert_eq!(std::iter::once(3).single().unwrap(), 3);
+    assert!(std::iter::empty::<u8>().single().is_err());
+}
@@ -0,0 +1,301 @@
+//! Fixed size bit sets and counting bits column by column.
+
+use std::{
+    fmt,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign},
+};
+
+/// A set of small integers, `0..64 * N`, stored as `N` words of bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::utils::BitSet;
+///
+/// // 2022 day 3, the item in both compartments of a rucksack
+/// let (left, right) = "vJrwpWtwJgWrhcsFMMfFFhFp".split_at(12);
+/// let common = BitSet::<1>::from_ascii_letters(left) & BitSet::from_ascii_letters(right);
+/// assert_eq!(common.iter().collect::<Vec<_>>(), [16]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<const N: usize> {
+    words: [u64; N],
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<const N: usize> BitSet<N> {
+    pub const EMPTY: Self = Self { words: [0; N] };
+    /// How many values fit, `0..CAPACITY`
+    pub const CAPACITY: usize = 64 * N;
+
+    pub fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// Letters by their priority, `a..=z` being 1 to 26 and `A..=Z` 27 to 52.
+    ///
+    /// Panics on anything else.
+    pub fn from_ascii_letters(s: &str) -> Self {
+        s.bytes()
+            .map(|b| match b {
+                b'a'..=b'z' => (b - b'a' + 1) as usize,
+                b'A'..=b'Z' => (b - b'A' + 27) as usize,
+                _ => panic!("not an ascii letter: {:?}", b as char),
+            })
+            .collect()
+    }
+
+    /// Returns whether `value` was newly inserted, panics if it doesn't fit
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(
+            value < Self::CAPACITY,
+            "{value} doesn't fit in a BitSet<{N}>"
+        );
+        let (word, bit) = (value / 64, 1 << (value % 64));
+        let new = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        new
+    }
+
+    /// Returns whether `value` was in the set
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was = self.contains(value);
+        if was {
+            self.words[value / 64] &= !(1 << (value % 64));
+        }
+        was
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && self.words[value / 64] & (1 << (value % 64)) != 0
+    }
+
+    /// Number of values in the set
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; N];
+    }
+
+    /// The smallest value
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(a, b)| a & !b == 0)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+
+    /// Values in ascending order
+    pub fn iter(&self) -> Iter<N> {
+        Iter {
+            words: self.words,
+            word: 0,
+        }
+    }
+
+    fn zip_with(mut self, other: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        for (a, b) in self.words.iter_mut().zip(other.words) {
+            *a = f(*a, b);
+        }
+        self
+    }
+}
+
+/// Iterator over a [`BitSet`], from [`BitSet::iter`]
+#[derive(Debug, Clone)]
+pub struct Iter<const N: usize> {
+    words: [u64; N],
+    word: usize,
+}
+
+impl<const N: usize> Iterator for Iter<N> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word < N {
+            let bits = &mut self.words[self.word];
+            if *bits != 0 {
+                let bit = bits.trailing_zeros() as usize;
+                // clear the lowest bit
+                *bits &= *bits - 1;
+                return Some(self.word * 64 + bit);
+            }
+            self.word += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.words[self.word.min(N)..]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum();
+        (left, Some(left))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for Iter<N> {}
+
+impl<const N: usize> IntoIterator for BitSet<N> {
+    type Item = usize;
+    type IntoIter = Iter<N>;
+
+    fn into_iter(self) -> Iter<N> {
+        self.iter()
+    }
+}
+
+impl<const N: usize> IntoIterator for &BitSet<N> {
+    type Item = usize;
+    type IntoIter = Iter<N>;
+
+    fn into_iter(self) -> Iter<N> {
+        self.iter()
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<const N: usize> Extend<usize> for BitSet<N> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+macro_rules! impl_set_op {
+    ($($op:ident $method:ident $assign:ident $assign_method:ident |$a:ident, $b:ident| $f:expr;)*) => {$(
+        impl<const N: usize> $op for BitSet<N> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                self.zip_with(other, |$a, $b| $f)
+            }
+        }
+
+        impl<const N: usize> $assign for BitSet<N> {
+            fn $assign_method(&mut self, other: Self) {
+                *self = self.zip_with(other, |$a, $b| $f);
+            }
+        }
+    )*};
+}
+
+impl_set_op! {
+    BitAnd bitand BitAndAssign bitand_assign |a, b| a & b;
+    BitOr bitor BitOrAssign bitor_assign |a, b| a | b;
+    BitXor bitxor BitXorAssign bitxor_assign |a, b| a ^ b;
+    Sub sub SubAssign sub_assign |a, b| a & !b;
+}
+
+impl<const N: usize> fmt::Debug for BitSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// How many of `numbers` have each of the lowest `width` bits set, lowest bit
+/// first
+pub fn bit_counts(numbers: &[u32], width: u32) -> Vec<usize> {
+    (0..width).map(|bit| count_ones_at(numbers, bit)).collect()
+}
+
+/// How many of `numbers` have bit `position` set
+pub fn count_ones_at(numbers: &[u32], position: u32) -> usize {
+    numbers
+        .iter()
+        .filter(|&&n| n.checked_shr(position).unwrap_or(0) & 1 == 1)
+        .count()
+}
+
+/// Whether bit `position` is set in most of `numbers`, ties count as set
+pub fn most_common_bit(numbers: &[u32], position: u32) -> bool {
+    count_ones_at(numbers, position) * 2 >= numbers.len()
+}
+
+/// Whether bit `position` is set in fewer than half of `numbers`, ties count
+/// as unset
+pub fn least_common_bit(numbers: &[u32], position: u32) -> bool {
+    !most_common_bit(numbers, position)
+}
+
+/// The most common value of each of the lowest `width` bits
+pub fn most_common_bits(numbers: &[u32], width: u32) -> u32 {
+    (0..width)
+        .filter(|&bit| most_common_bit(numbers, bit))
+        .fold(0, |acc, bit| acc | 1 << bit)
+}
+
+/// The least common value of each of the lowest `width` bits
+pub fn least_common_bits(numbers: &[u32], width: u32) -> u32 {
+    (0..width)
+        .filter(|&bit| least_common_bit(numbers, bit))
+        .fold(0, |acc, bit| acc | 1 << bit)
+}
+
+#[test]
+fn test_bitset() {
+    let mut a = BitSet::<2>::new();
+    assert!(a.insert(3) && a.insert(64) && a.insert(127));
+    assert!(!a.insert(3));
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.iter().collect::<Vec<_>>(), [3, 64, 127]);
+    assert_eq!(a.iter().len(), 3);
+    assert!(!a.contains(128) && a.contains(64));
+
+    let b: BitSet<2> = [3, 4, 100].into_iter().collect();
+    assert_eq!((a & b).iter().collect::<Vec<_>>(), [3]);
+    assert_eq!((a | b).len(), 5);
+    assert_eq!((a ^ b).iter().collect::<Vec<_>>(), [4, 64, 100, 127]);
+    assert_eq!((a - b).iter().collect::<Vec<_>>(), [64, 127]);
+    assert!((a & b).is_subset(&a) && !a.is_subset(&b));
+    assert!((a - b).is_disjoint(&b));
+    assert!(a.remove(64) && !a.remove(64));
+    assert_eq!(format!("{a:?}"), "{3, 127}");
+
+    let letters = BitSet::<1>::from_ascii_letters("azAZ");
+    assert_eq!(letters.iter().collect::<Vec<_>>(), [1, 26, 27, 52]);
+    assert_eq!(letters.first(), Some(1));
+    assert!(BitSet::<1>::EMPTY.is_empty());
+}
+
+#[test]
+fn test_bit_counts() {
+    // 2021 day 3 example
+    let report = [
+        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
+        0b00010, 0b01010,
+    ];
+    assert_eq!(bit_counts(&report, 5), [5, 7, 8, 5, 7]);
+    assert_eq!(most_common_bits(&report, 5), 0b10110);
+    assert_eq!(least_common_bits(&report, 5), 0b01001);
+    assert!(most_common_bit(&[0b1, 0b0], 0));
+    assert!(!least_common_bit(&[0b1, 0b0], 0));
+    assert_eq!(count_ones_at(&report, 40), 0);
+}
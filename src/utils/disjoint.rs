@@ -0,0 +1,293 @@
+//! Borrowing several elements of a collection mutably at once.
+
+use std::{
+    array,
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+    ops::Bound,
+};
+
+use eyre::Report;
+
+use super::GridIndex;
+
+/// Collections that lend out several of their elements mutably at once, e.g.
+/// to swap or move values between them.
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::utils::DisjointMut;
+///
+/// // 2022 day 5, moving crates between stacks
+/// let mut stacks = vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']];
+/// let [from, to] = stacks.disjoint_mut([1, 0])?;
+/// to.push(from.pop().unwrap());
+/// assert_eq!(stacks, [vec!['Z', 'N', 'D'], vec!['M', 'C'], vec!['P']]);
+///
+/// let [a, b] = stacks.disjoint_mut([(0, 0), (2, 0)])?;
+/// std::mem::swap(a, b);
+/// assert_eq!(stacks[2], ['Z']);
+/// assert!(stacks.disjoint_mut([(1, 1), (1, 1)]).is_err());
+/// # Ok::<(), eyre::Report>(())
+/// ```
+pub trait DisjointMut<I> {
+    type Output: ?Sized;
+
+    /// The elements at `indices`, an error if one is missing or given twice
+    fn disjoint_mut<const N: usize>(
+        &mut self,
+        indices: [I; N],
+    ) -> Result<[&mut Self::Output; N], Report>;
+
+    /// The elements at `indices`, without checking them
+    ///
+    /// # Safety
+    ///
+    /// Every index must be present and different from all the others.
+    unsafe fn disjoint_mut_unchecked<const N: usize>(
+        &mut self,
+        indices: [I; N],
+    ) -> [&mut Self::Output; N];
+}
+
+/// Item `index` of `items`, which has already handed out everything before
+/// `*next`. `None` if it's past the end or was handed out already.
+fn take_at<'a, T: 'a>(
+    items: &mut impl Iterator<Item = &'a mut T>,
+    next: &mut usize,
+    index: usize,
+) -> Option<&'a mut T> {
+    let item = items.nth(index.checked_sub(*next)?)?;
+    *next = index + 1;
+    Some(item)
+}
+
+/// The order to visit `keys` in, sorted
+fn sorted_order<K: Ord, const N: usize>(keys: &[K; N]) -> [usize; N] {
+    let mut order = array::from_fn(|k| k);
+    order.sort_unstable_by_key(|&k| &keys[k]);
+    order
+}
+
+impl<T> DisjointMut<usize> for [T] {
+    type Output = T;
+
+    fn disjoint_mut<const N: usize>(&mut self, indices: [usize; N]) -> Result<[&mut T; N], Report> {
+        self.get_disjoint_mut(indices).map_err(Into::into)
+    }
+
+    unsafe fn disjoint_mut_unchecked<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> [&mut T; N] {
+        // SAFETY: the caller upholds the same contract
+        unsafe { self.get_disjoint_unchecked_mut(indices) }
+    }
+}
+
+/// The cells at `(row, column)` `indices` of `rows`, walking the rows and
+/// cells in order so each is handed out at most once
+fn rows_disjoint_mut<C: AsMut<[T]>, T, const N: usize>(
+    rows: &mut [C],
+    indices: [(usize, usize); N],
+) -> Result<[&mut T; N], Report> {
+    let mut picked: [Option<&mut T>; N] = array::from_fn(|_| None);
+    let (mut rows, mut next_row) = (rows.iter_mut(), 0);
+    let mut row = None;
+    for k in sorted_order(&indices) {
+        let (i, j) = indices[k];
+        let (cells, next_cell) = match &mut row {
+            Some((current, cells, next_cell)) if *current == i => (cells, next_cell),
+            _ => {
+                let Some(cells) = take_at(&mut rows, &mut next_row, i) else {
+                    eyre::bail!("index {i}, {j} out of bounds")
+                };
+                let (_, cells, next_cell) = row.insert((i, cells.as_mut().iter_mut(), 0));
+                (cells, next_cell)
+            }
+        };
+        let Some(cell) = take_at(cells, next_cell, j) else {
+            eyre::bail!("index {i}, {j} out of bounds or given twice")
+        };
+        picked[k] = Some(cell);
+    }
+    Ok(picked.map(|cell| cell.expect("every index is picked")))
+}
+
+/// Nested rows like `Vec<Vec<T>>`, indexed by row then column
+impl<T, I: GridIndex> DisjointMut<I> for [Vec<T>] {
+    type Output = T;
+
+    fn disjoint_mut<const N: usize>(&mut self, indices: [I; N]) -> Result<[&mut T; N], Report> {
+        rows_disjoint_mut(self, indices.map(GridIndex::outer_inner))
+    }
+
+    unsafe fn disjoint_mut_unchecked<const N: usize>(&mut self, indices: [I; N]) -> [&mut T; N] {
+        let indices = indices.map(GridIndex::outer_inner);
+        let rows = self.as_mut_ptr();
+        // reborrowing a row for every index would invalidate the cells handed
+        // out of it before, so every row is borrowed once
+        let mut row_ptrs = [std::ptr::null_mut::<T>(); N];
+        array::from_fn(|k| {
+            let (i, j) = indices[k];
+            row_ptrs[k] = match (0..k).find(|&l| indices[l].0 == i) {
+                Some(l) => row_ptrs[l],
+                // SAFETY: the caller guarantees `i` is in bounds
+                None => unsafe { (*rows.add(i)).as_mut_ptr() },
+            };
+            // SAFETY: the caller guarantees `j` is in bounds and no other
+            // index points at the same cell
+            unsafe { &mut *row_ptrs[k].add(j) }
+        })
+    }
+}
+
+/// Takes indices into e.g a `Vec<Vec<T>>` and returns them mutably
+///
+/// Indices are `(outer, inner)` tuples or [`Point2`](super::Point2)s, see
+/// [`GridIndex`].
+#[deprecated(note = "use `DisjointMut::disjoint_mut` instead")]
+pub trait ArrayArrayTools<C, T> {
+    fn double_disjoint_mut<I: GridIndex, const N: usize>(
+        &mut self,
+        indices: [I; N],
+    ) -> Result<[&mut T; N], Report>;
+}
+
+#[allow(deprecated)]
+impl<C, T> ArrayArrayTools<C, T> for [C]
+where
+    C: AsMut<[T]> + AsRef<[T]>,
+{
+    fn double_disjoint_mut<I: GridIndex, const N: usize>(
+        &mut self,
+        indices: [I; N],
+    ) -> Result<[&mut T; N], Report> {
+        rows_disjoint_mut(self, indices.map(GridIndex::outer_inner))
+    }
+}
+
+impl<K, Q, V, S> DisjointMut<&Q> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    fn disjoint_mut<const N: usize>(&mut self, keys: [&Q; N]) -> Result<[&mut V; N], Report> {
+        for (k, key) in keys.iter().enumerate() {
+            if keys[..k].contains(key) {
+                eyre::bail!("key {k} given twice");
+            }
+        }
+        let values = self.get_disjoint_mut(keys);
+        if let Some(k) = values.iter().position(Option::is_none) {
+            eyre::bail!("key {k} not in the map");
+        }
+        Ok(values.map(|value| value.expect("every key is present")))
+    }
+
+    unsafe fn disjoint_mut_unchecked<const N: usize>(&mut self, keys: [&Q; N]) -> [&mut V; N] {
+        // SAFETY: the caller guarantees the keys are distinct and present
+        unsafe { self.get_disjoint_unchecked_mut(keys) }
+            .map(|value| unsafe { value.unwrap_unchecked() })
+    }
+}
+
+/// The unchecked path is the checked one, a `BTreeMap` has no way to get at
+/// several values without walking the entries between the smallest and largest
+/// key, and that walk finds missing and repeated keys for free.
+impl<K, Q, V> DisjointMut<&Q> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    fn disjoint_mut<const N: usize>(&mut self, keys: [&Q; N]) -> Result<[&mut V; N], Report> {
+        let order = sorted_order(&keys);
+        let Some(&first) = order.first() else {
+            return Ok(array::from_fn(|_| unreachable!("no keys")));
+        };
+        let mut entries = self.range_mut::<Q, _>((Bound::Included(keys[first]), Bound::Unbounded));
+        let mut picked: [Option<&mut V>; N] = array::from_fn(|_| None);
+        for (n, &k) in order.iter().enumerate() {
+            if n > 0 && keys[order[n - 1]] == keys[k] {
+                eyre::bail!("key {k} given twice");
+            }
+            let found = entries.find(|(key, _)| (*key).borrow() >= keys[k]);
+            let Some((_, value)) = found.filter(|(key, _)| (*key).borrow() == keys[k]) else {
+                eyre::bail!("key {k} not in the map");
+            };
+            picked[k] = Some(value);
+        }
+        Ok(picked.map(|value| value.expect("every key is picked")))
+    }
+
+    unsafe fn disjoint_mut_unchecked<const N: usize>(&mut self, keys: [&Q; N]) -> [&mut V; N] {
+        // no faster than the checked path, see above
+        self.disjoint_mut(keys)
+            .expect("keys are distinct and present")
+    }
+}
+
+#[test]
+fn test_disjoint_mut_rows() {
+    let mut v: Vec<Vec<u8>> = vec![vec![1, 2, 3, 4], vec![4, 3, 2, 1]];
+    assert!(v.disjoint_mut([(0, 0), (0, 1)]).is_ok());
+    let [a, b] = v.disjoint_mut([(0, 0), (1, 0)]).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(v, vec![vec![4, 2, 3, 4], vec![1, 3, 2, 1]]);
+
+    let mut v: Vec<Vec<u8>> = vec![vec![1, 2, 3, 4], vec![4, 3, 2, 1]];
+    assert!(v.disjoint_mut([(0, 4), (1, 0)]).is_err());
+    assert!(v.disjoint_mut([(0, 0), (4, 0)]).is_err());
+    v.disjoint_mut([(0, 0), (0, 3), (1, 0), (1, 1), (1, 2)])
+        .unwrap();
+    assert!(v.disjoint_mut([(0, 4), (0, 4)]).is_err());
+
+    let [a, b] = v
+        .disjoint_mut([super::Point2::new(3, 0), super::Point2::new(0, 1)])
+        .unwrap();
+    assert_eq!((*a, *b), (4, 4));
+
+    // SAFETY: in bounds and distinct, cells of the same row included
+    let [a, b, c] = unsafe { v.disjoint_mut_unchecked([(1, 3), (0, 0), (1, 0)]) };
+    std::mem::swap(a, c);
+    *b += 10;
+    assert_eq!(v, vec![vec![11, 2, 3, 4], vec![1, 3, 2, 4]]);
+
+    let mut flat = [1, 2, 3];
+    assert!(flat.disjoint_mut([0, 0]).is_err());
+    let [a, b] = flat.disjoint_mut([2, 0]).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(flat, [3, 2, 1]);
+}
+
+#[test]
+fn test_disjoint_mut_maps() {
+    let mut hash: HashMap<String, u32> = [("a".to_owned(), 1), ("b".to_owned(), 2)].into();
+    let [a, b] = hash.disjoint_mut(["a", "b"]).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(hash["a"], 2);
+    assert!(hash.disjoint_mut(["a", "a"]).is_err());
+    assert!(hash.disjoint_mut(["a", "c"]).is_err());
+    // SAFETY: both keys are present
+    let [b] = unsafe { hash.disjoint_mut_unchecked(["b"]) };
+    *b = 5;
+    assert_eq!(hash["b"], 5);
+
+    let mut tree: BTreeMap<u32, char> = (0..10).zip('a'..).collect();
+    let [x, y, z] = tree.disjoint_mut([&7, &2, &9]).unwrap();
+    assert_eq!((*x, *y, *z), ('h', 'c', 'j'));
+    *y = 'z';
+    assert_eq!(tree[&2], 'z');
+    assert!(tree.disjoint_mut([&3, &3]).is_err());
+    assert!(tree.disjoint_mut([&3, &10]).is_err());
+    tree.remove(&5);
+    assert!(tree.disjoint_mut([&5, &6]).is_err());
+    assert!(tree.disjoint_mut::<0>([]).is_ok());
+}
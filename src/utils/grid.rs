@@ -0,0 +1,496 @@
+//! A two dimensional grid, as found in many puzzles.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use eyre::Report;
+use owo_colors::OwoColorize;
+
+use super::{DisjointMut, Point2};
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// Positions are `(x, y)`, with `x` the column and `y` the row, starting from
+/// the top left.
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::Grid;
+///
+/// let grid: Grid<u32> = Grid::parse("123\n456", |c| {
+///     c.to_digit(10).ok_or_else(|| eyre::eyre!("not a digit: {c:?}"))
+/// })?;
+/// assert_eq!(grid[(2, 0)], 3);
+/// assert_eq!(grid.get(3, 0), None);
+/// assert_eq!(grid.neighbours4(0, 0).map(|p| grid[p]).sum::<u32>(), 2 + 4);
+/// assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from its cells, row by row.
+    pub fn new(width: usize, cells: Vec<T>) -> Result<Self, Report> {
+        if width == 0 && !cells.is_empty() || width != 0 && !cells.len().is_multiple_of(width) {
+            eyre::bail!("{} cells don't fit in rows of {width}", cells.len());
+        }
+        Ok(Self {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    /// Create a grid with every cell set to `value`.
+    ///
+    /// A grid without columns has no rows either.
+    pub fn from_elem(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone, {
+        let height = if width == 0 { 0 } else { height };
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Create a grid by calling `f` with the position of every cell.
+    ///
+    /// A grid without columns has no rows either.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let height = if width == 0 { 0 } else { height };
+        Self {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| f(x, y))
+                .collect(),
+        }
+    }
+
+    /// Parse a grid from lines of text, converting every character with `f`.
+    ///
+    /// Only trailing newlines are dropped, spaces are cells like any other
+    /// character. All lines must be equally long.
+    pub fn parse(s: &str, mut f: impl FnMut(char) -> Result<T, Report>) -> Result<Self, Report> {
+        let mut width = None;
+        let mut cells = vec![];
+        for (y, line) in s.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let len = line.chars().count();
+            if *width.get_or_insert(len) != len {
+                eyre::bail!("line {y} is {len} long, expected {}", width.unwrap_or(0));
+            }
+            for c in line.chars() {
+                cells.push(f(c)?);
+            }
+        }
+        Self::new(width.unwrap_or(0), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is inside the grid
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Index into [`cells`](Self::cells) of `(x, y)`
+    pub fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        self.contains(x, y).then(|| y * self.width + x)
+    }
+
+    /// Position of the cell at `index` in [`cells`](Self::cells), `None` if
+    /// there is no such cell
+    pub fn position_of(&self, index: usize) -> Option<(usize, usize)> {
+        (index < self.cells.len()).then(|| (index % self.width, index / self.width))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// All cells, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn into_cells(self) -> Vec<T> {
+        self.cells
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + Clone {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    /// Find the position of the first cell matching `predicate`
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .and_then(|i| self.position_of(i))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        (y < self.height).then(|| &mut self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // `chunks` panics on 0, an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Cells in column `x`, from the top
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Positions of the up to 4 orthogonal neighbours of `(x, y)` in the grid
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &[(0, -1), (1, 0), (0, 1), (-1, 0)])
+    }
+
+    /// Positions of the up to 8 orthogonal and diagonal neighbours of `(x, y)`
+    /// in the grid
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(
+            x,
+            y,
+            &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+            ],
+        )
+    }
+
+    fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        deltas: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        deltas.iter().filter_map(move |&(dx, dy)| {
+            let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            (x < width && y < height).then_some((x, y))
+        })
+    }
+
+    /// Apply `f` to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Swap rows and columns
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone, {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Rotate a quarter turn clockwise
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone, {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Rotate a quarter turn counter-clockwise
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone, {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone, {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone, {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+
+    /// Display the grid with the cells matching `highlight` in red.
+    pub fn highlight<F>(&self, highlight: F) -> Highlight<'_, T, F>
+    where
+        F: Fn((usize, usize), &T) -> bool, {
+        Highlight {
+            grid: self,
+            highlight,
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parse a grid of characters as is
+    pub fn parse_chars(s: &str) -> Result<Self, Report> {
+        Self::parse(s, Ok)
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_chars(s)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| {
+            panic!(
+                "position ({x}, {y}) out of bounds for grid of {}x{}",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point2<usize>) -> &T {
+        &self[(p.x, p.y)]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, p: Point2<usize>) -> &mut T {
+        &mut self[(p.x, p.y)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y).unwrap_or_else(|| {
+            panic!("position ({x}, {y}) out of bounds for grid of {width}x{height}")
+        })
+    }
+}
+
+/// Cells by `(x, y)` position
+impl<T> DisjointMut<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn disjoint_mut<const N: usize>(
+        &mut self,
+        positions: [(usize, usize); N],
+    ) -> Result<[&mut T; N], Report> {
+        let mut indices = [0; N];
+        for (index, (x, y)) in indices.iter_mut().zip(positions) {
+            let Some(i) = self.index_of(x, y) else {
+                eyre::bail!(
+                    "position ({x}, {y}) out of bounds for grid of {}x{}",
+                    self.width,
+                    self.height
+                );
+            };
+            *index = i;
+        }
+        self.cells.disjoint_mut(indices)
+    }
+
+    unsafe fn disjoint_mut_unchecked<const N: usize>(
+        &mut self,
+        positions: [(usize, usize); N],
+    ) -> [&mut T; N] {
+        let indices = positions.map(|(x, y)| y * self.width + x);
+        // SAFETY: the caller upholds the same contract
+        unsafe { self.cells.disjoint_mut_unchecked(indices) }
+    }
+}
+
+impl<T> DisjointMut<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn disjoint_mut<const N: usize>(
+        &mut self,
+        positions: [Point2<usize>; N],
+    ) -> Result<[&mut T; N], Report> {
+        self.disjoint_mut(positions.map(|p| (p.x, p.y)))
+    }
+
+    unsafe fn disjoint_mut_unchecked<const N: usize>(
+        &mut self,
+        positions: [Point2<usize>; N],
+    ) -> [&mut T; N] {
+        // SAFETY: the caller upholds the same contract
+        unsafe { self.disjoint_mut_unchecked(positions.map(|p| (p.x, p.y))) }
+    }
+}
+
+/// Prints every cell with the given formatting options, e.g. `{:>3}` to pad
+/// numbers, one row per line.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.highlight(|_, _| false).fmt(f)
+    }
+}
+
+/// [`Display`](fmt::Display) of a [`Grid`] with highlighted cells, see
+/// [`Grid::highlight`]
+pub struct Highlight<'a, T, F> {
+    grid: &'a Grid<T>,
+    highlight: F,
+}
+
+impl<T, F> fmt::Display for Highlight<'_, T, F>
+where
+    T: fmt::Display,
+    F: Fn((usize, usize), &T) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.grid.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, cell) in row.iter().enumerate() {
+                if (self.highlight)((x, y), cell) {
+                    fmt::Display::fmt(&cell.red(), f)?;
+                } else {
+                    fmt::Display::fmt(cell, f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_grid() {
+    let grid = Grid::parse_chars("abc\ndef\n").unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.get(1, 1), Some(&'e'));
+    assert_eq!(grid.get(1, 2), None);
+    assert_eq!(grid.position(|&c| c == 'f'), Some((2, 1)));
+    assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+    assert_eq!(grid.column(2).collect::<String>(), "cf");
+    assert_eq!(
+        grid.columns()
+            .map(|c| c.rev().collect())
+            .collect::<Vec<String>>(),
+        ["da", "eb", "fc"]
+    );
+    assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    assert_eq!(grid.neighbours8(1, 1).count(), 5);
+    assert_eq!(grid[Point2::new(0, 1)], 'd');
+
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+    assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+    assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+    assert_eq!(grid.rotate_left().rotate_right(), grid);
+    assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+    assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+
+    let numbers = grid.map(|&c| c as u32 - 'a' as u32);
+    assert_eq!(format!("{numbers:>2}"), " 0 1 2\n 3 4 5");
+    let highlighted = numbers.highlight(|_, &n| n == 4).to_string();
+    assert!(highlighted.starts_with("012\n3"));
+    assert!(highlighted.contains(&4.red().to_string()));
+
+    let mut swapped = grid.clone();
+    let [a, f] = swapped.disjoint_mut([(0, 0), (2, 1)]).unwrap();
+    std::mem::swap(a, f);
+    assert_eq!(swapped.to_string(), "fbc\ndea");
+    assert!(swapped.disjoint_mut([(3, 0), (0, 0)]).is_err());
+    assert!(swapped
+        .disjoint_mut([Point2::new(1, 1), Point2::new(1, 1)])
+        .is_err());
+
+    let padded = Grid::parse_chars("  #\n#  \n").unwrap();
+    assert_eq!(
+        (padded.width(), padded[(0, 0)], padded[(2, 1)]),
+        (3, ' ', ' ')
+    );
+    assert_eq!(padded.position_of(5), Some((2, 1)));
+    assert_eq!(padded.position_of(6), None);
+
+    assert!(Grid::parse_chars("ab\nc").is_err());
+    assert!(Grid::new(2, vec![1, 2, 3]).is_err());
+    let empty = Grid::<char>::parse_chars("").unwrap();
+    assert_eq!(
+        (empty.width(), empty.height(), empty.rows().len()),
+        (0, 0, 0)
+    );
+    assert_eq!(empty.position_of(0), None);
+    let columnless = Grid::from_elem(0, 3, '.');
+    assert_eq!(columnless, empty);
+    assert_eq!(columnless.height(), columnless.rows().count());
+    assert_eq!(Grid::from_fn(0, 3, |_, _| '.'), empty);
+}
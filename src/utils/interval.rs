@@ -0,0 +1,301 @@
+//! Sets of integers stored as sorted, merged ranges.
+
+use std::{
+    fmt,
+    ops::{Bound, RangeBounds, RangeInclusive},
+};
+
+/// Integers an [`IntervalSet`] can hold
+pub trait Int: Copy + Ord + fmt::Debug {
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+    /// Number of integers in `start..=end`
+    fn width(start: Self, end: Self) -> u128;
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn checked_succ(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn checked_pred(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn width(start: Self, end: Self) -> u128 {
+                (end as i128 - start as i128) as u128 + 1
+            }
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(value: i128) -> Option<Self> {
+                value.try_into().ok()
+            }
+        }
+    )*};
+}
+
+impl_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers, kept as sorted ranges that neither overlap nor touch.
+///
+/// Ranges can be given with any bounds, `a..b`, `a..=b`, `a..` or `..`, and
+/// are stored inclusive so the whole domain of `T` fits.
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::utils::IntervalSet;
+///
+/// let mut set: IntervalSet<u64> = [1..5, 3..8, 10..12].into_iter().collect();
+/// assert_eq!(set.ranges().collect::<Vec<_>>(), [1..=7, 10..=11]);
+/// assert_eq!(set.len(), 9);
+/// assert_eq!(set.gaps().collect::<Vec<_>>(), [8..=9]);
+///
+/// set.remove(..=2);
+/// assert!(set.contains_range(3..=7));
+/// assert_eq!(set.complement(..).len(), (1 << 64) - 7);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Inclusive `(start, end)`, sorted with gaps between them
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: vec![] }
+    }
+}
+
+impl<T: Int> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `start..=end` of `range`, `None` if it's empty
+    fn bounds(range: impl RangeBounds<T>) -> Option<(T, T)> {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.checked_succ()?,
+            Bound::Unbounded => T::MIN,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e,
+            Bound::Excluded(&e) => e.checked_pred()?,
+            Bound::Unbounded => T::MAX,
+        };
+        (start <= end).then_some((start, end))
+    }
+
+    /// Merge ranges sorted by start
+    fn from_sorted(sorted: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut ranges: Vec<(T, T)> = vec![];
+        for (start, end) in sorted {
+            match ranges.last_mut() {
+                // overlapping or touching, `None` meaning the last one reaches MAX
+                Some(last) if last.1.checked_succ().is_none_or(|after| start <= after) => {
+                    last.1 = last.1.max(end)
+                }
+                _ => ranges.push((start, end)),
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn from_range(range: impl RangeBounds<T>) -> Self {
+        Self::from_sorted(Self::bounds(range))
+    }
+
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        *self = self.union(&Self::from_range(range));
+    }
+
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        *self = self.difference(&Self::from_range(range));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many integers are in the set
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(|&(s, e)| T::width(s, e)).sum()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, end)| end < value);
+        self.ranges.get(i).is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Whether all of `range` is in the set
+    pub fn contains_range(&self, range: impl RangeBounds<T>) -> bool {
+        let Some((start, end)) = Self::bounds(range) else {
+            return true;
+        };
+        let i = self.ranges.partition_point(|&(_, e)| e < start);
+        self.ranges
+            .get(i)
+            .is_some_and(|&(s, e)| s <= start && end <= e)
+    }
+
+    /// Whether any of `range` is in the set
+    pub fn overlaps(&self, range: impl RangeBounds<T>) -> bool {
+        let Some((start, end)) = Self::bounds(range) else {
+            return false;
+        };
+        let i = self.ranges.partition_point(|&(_, e)| e < start);
+        self.ranges.get(i).is_some_and(|&(s, _)| s <= end)
+    }
+
+    /// The ranges of the set, in order
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// The ranges missing between the first and last range of the set
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        // ranges never touch, so these can't overflow
+        self.ranges.windows(2).map(|w| {
+            w[0].1
+                .checked_succ()
+                .expect("range before a gap ends at MAX")
+                ..=w[1]
+                    .0
+                    .checked_pred()
+                    .expect("range after a gap starts at MIN")
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut all = [self.ranges.as_slice(), other.ranges.as_slice()].concat();
+        all.sort_unstable();
+        Self::from_sorted(all)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        let mut ranges = vec![];
+        while let (Some(&&(s1, e1)), Some(&&(s2, e2))) = (a.peek(), b.peek()) {
+            let (start, end) = (s1.max(s2), e1.min(e2));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if e1 < e2 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement(..))
+    }
+
+    /// Everything in `bounds` that isn't in the set
+    pub fn complement(&self, bounds: impl RangeBounds<T>) -> Self {
+        let Some((low, high)) = Self::bounds(bounds) else {
+            return Self::new();
+        };
+        let mut ranges = vec![];
+        let mut next = Some(low);
+        for &(start, end) in &self.ranges {
+            let Some(from) = next else { break };
+            if start > from {
+                ranges.push((from, start.checked_pred().expect("start > MIN").min(high)));
+            }
+            next = end.checked_succ().map(|after| after.max(from));
+            if end >= high {
+                next = None;
+            }
+        }
+        if let Some(from) = next {
+            ranges.push((from, high));
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Int, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut all: Vec<_> = iter.into_iter().filter_map(Self::bounds).collect();
+        all.sort_unstable();
+        Self::from_sorted(all)
+    }
+}
+
+impl<T: Int, R: RangeBounds<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        *self = self.union(&iter.into_iter().collect());
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.ranges.iter().map(|(s, e)| s..=e))
+            .finish()
+    }
+}
+
+#[test]
+fn test_interval_set() {
+    let set = |ranges: &[RangeInclusive<i32>]| ranges.iter().cloned().collect::<IntervalSet<i32>>();
+    let a = set(&[1..=5, 10..=20]);
+    let b = set(&[4..=12, 18..=25]);
+
+    assert_eq!(a.union(&b), set(&[1..=25]));
+    assert_eq!(a.intersection(&b), set(&[4..=5, 10..=12, 18..=20]));
+    assert_eq!(a.difference(&b), set(&[1..=3, 13..=17]));
+    assert_eq!(b.difference(&a), set(&[6..=9, 21..=25]));
+    assert_eq!(a.complement(0..30), set(&[0..=0, 6..=9, 21..=29]));
+    assert_eq!(a.complement(3..=12), set(&[6..=9]));
+    assert_eq!(a.complement(..), set(&[i32::MIN..=0, 6..=9, 21..=i32::MAX]));
+    assert_eq!(a.len(), 16);
+    assert_eq!(a.gaps().collect::<Vec<_>>(), [6..=9]);
+
+    // touching ranges merge, empty ones are dropped
+    let mut c = IntervalSet::from_range(1..3);
+    c.insert(3..=4);
+    c.insert(7..7);
+    assert_eq!(c, set(&[1..=4]));
+    c.remove(2..4);
+    assert_eq!(c, set(&[1..=1, 4..=4]));
+    assert!(c.contains(4) && !c.contains(3));
+    assert!(a.contains_range(11..15) && !a.contains_range(4..7));
+    assert!(a.overlaps(5..7) && !a.overlaps(6..10));
+
+    // full domain without overflow
+    let full = IntervalSet::<u64>::from_range(..);
+    assert_eq!(full.len(), 1 << 64);
+    assert!(full.complement(..).is_empty());
+    let mut edges = IntervalSet::<u64>::new();
+    edges.extend([0..=0, u64::MAX..=u64::MAX]);
+    assert_eq!(
+        edges.complement(..).ranges().collect::<Vec<_>>(),
+        [1..=u64::MAX - 1]
+    );
+    assert_eq!(full.difference(&edges), edges.complement(..));
+    assert_eq!(
+        IntervalSet::<u8>::from_range(250..).union(&IntervalSet::from_range(..=10)),
+        IntervalSet::from_iter([0..=10, 250..=255])
+    );
+}
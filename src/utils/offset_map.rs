@@ -0,0 +1,230 @@
+//! Maps that shift ranges of `u64` by an offset each, like the almanac of 2023
+//! day 5.
+
+use std::{
+    fmt,
+    ops::{RangeBounds, RangeInclusive},
+};
+
+use eyre::Report;
+
+use super::IntervalSet;
+
+/// A function on `u64` made of ranges that are each shifted by an offset,
+/// anything outside of them maps to itself.
+///
+/// Whole ranges can be pushed through at once, and maps compose, so a chain
+/// of maps collapses into a single one.
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::utils::{IntervalSet, OffsetMap};
+///
+/// // seed-to-soil map of 2023 day 5
+/// let mut soil = OffsetMap::new();
+/// soil.insert(98..100, 50)?;
+/// soil.insert(50..98, 52)?;
+/// assert_eq!(soil.get(79), 81);
+/// assert_eq!(soil.get(10), 10);
+/// assert_eq!(soil.map_range(95..=100), [97..=99, 50..=51, 100..=100]);
+///
+/// // soil-to-fertilizer
+/// let mut fertilizer = OffsetMap::new();
+/// fertilizer.insert(15..52, 0)?;
+/// fertilizer.insert(52..54, 37)?;
+/// fertilizer.insert(0..15, 39)?;
+/// let both = soil.then(&fertilizer);
+/// assert_eq!(both.get(79), fertilizer.get(81));
+/// assert_eq!(both.inverse().unwrap().get(both.get(14)), 14);
+///
+/// let seeds = IntervalSet::from_iter([79..93, 55..68]);
+/// assert_eq!(both.map_set(&seeds).ranges().next(), Some(57..=69));
+/// # Ok::<(), eyre::Report>(())
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct OffsetMap {
+    /// Inclusive `(start, end, offset)`, sorted and disjoint. No offset is 0
+    /// and neighbours with the same offset are merged.
+    pieces: Vec<(u64, u64, i128)>,
+}
+
+impl OffsetMap {
+    /// The identity map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort and merge pieces that are already disjoint
+    fn from_pieces(mut pieces: Vec<(u64, u64, i128)>) -> Self {
+        pieces.retain(|&(_, _, offset)| offset != 0);
+        pieces.sort_unstable();
+        let mut merged: Vec<(u64, u64, i128)> = vec![];
+        for (start, end, offset) in pieces {
+            match merged.last_mut() {
+                Some(last) if last.2 == offset && last.1.checked_add(1) == Some(start) => {
+                    last.1 = end
+                }
+                _ => merged.push((start, end, offset)),
+            }
+        }
+        Self { pieces: merged }
+    }
+
+    /// The pieces and the identity between them, covering all of `u64`
+    fn segments(&self) -> impl Iterator<Item = (u64, u64, i128)> + '_ {
+        let mut next = Some(0u64);
+        let mut pieces = self.pieces.iter().copied().peekable();
+        std::iter::from_fn(move || {
+            let from = next?;
+            let segment = match pieces.peek() {
+                Some(&(start, ..)) if start > from => (from, start - 1, 0),
+                Some(_) => pieces.next().expect("just peeked"),
+                None => (from, u64::MAX, 0),
+            };
+            next = segment.1.checked_add(1);
+            Some(segment)
+        })
+    }
+
+    /// Map `source` onto the range of the same length starting at
+    /// `destination`, replacing what `source` mapped to before.
+    pub fn insert(
+        &mut self,
+        source: impl RangeBounds<u64>,
+        destination: u64,
+    ) -> Result<(), Report> {
+        let Some(source) = IntervalSet::from_range(source).ranges().next() else {
+            return Ok(());
+        };
+        let (start, end) = source.into_inner();
+        let offset = destination as i128 - start as i128;
+        if end as i128 + offset > u64::MAX as i128 {
+            eyre::bail!("{start}..={end} shifted to {destination} doesn't fit in a u64");
+        }
+        let mut pieces = vec![];
+        for (s, e, o) in self.pieces.iter().copied() {
+            if s < start {
+                pieces.push((s, e.min(start - 1), o));
+            }
+            if e > end {
+                pieces.push((s.max(end + 1), e, o));
+            }
+        }
+        pieces.push((start, end, offset));
+        *self = Self::from_pieces(pieces);
+        Ok(())
+    }
+
+    pub fn get(&self, value: u64) -> u64 {
+        let i = self.pieces.partition_point(|&(_, end, _)| end < value);
+        match self.pieces.get(i) {
+            Some(&(start, _, offset)) if start <= value => (value as i128 + offset) as u64,
+            _ => value,
+        }
+    }
+
+    /// Where the parts of `range` end up, in the order of `range`
+    pub fn map_range(&self, range: impl RangeBounds<u64>) -> Vec<RangeInclusive<u64>> {
+        let Some((start, end)) = IntervalSet::from_range(range)
+            .ranges()
+            .next()
+            .map(RangeInclusive::into_inner)
+        else {
+            return vec![];
+        };
+        self.segments()
+            .skip_while(|&(_, e, _)| e < start)
+            .take_while(|&(s, ..)| s <= end)
+            .map(|(s, e, offset)| {
+                let shift = |v: u64| (v as i128 + offset) as u64;
+                shift(s.max(start))..=shift(e.min(end))
+            })
+            .collect()
+    }
+
+    /// Where all of `set` ends up
+    pub fn map_set(&self, set: &IntervalSet<u64>) -> IntervalSet<u64> {
+        set.ranges()
+            .flat_map(|range| self.map_range(range))
+            .collect()
+    }
+
+    /// The map applying `self`, then `next`
+    pub fn then(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+        for (start, end, offset) in self.segments() {
+            let shift = |v: u64, by: i128| (v as i128 + by) as u64;
+            for (s, e, o) in next
+                .segments()
+                .skip_while(|&(_, e, _)| e < shift(start, offset))
+                .take_while(|&(s, ..)| s <= shift(end, offset))
+            {
+                pieces.push((
+                    shift(s.max(shift(start, offset)), -offset),
+                    shift(e.min(shift(end, offset)), -offset),
+                    offset + o,
+                ));
+            }
+        }
+        Self::from_pieces(pieces)
+    }
+
+    /// The map undoing `self`, `None` if two values map to the same one
+    pub fn inverse(&self) -> Option<Self> {
+        let mut images: Vec<_> = self
+            .segments()
+            .map(|(s, e, offset)| {
+                let shift = |v: u64| (v as i128 + offset) as u64;
+                (shift(s), shift(e), -offset)
+            })
+            .collect();
+        images.sort_unstable();
+        // the segments cover all of u64, so their images do too unless some overlap
+        let bijective = images.windows(2).all(|w| w[0].1 < w[1].0);
+        bijective.then(|| Self::from_pieces(images))
+    }
+}
+
+impl fmt::Debug for OffsetMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.pieces.iter().map(|(s, e, offset)| (s..=e, offset)))
+            .finish()
+    }
+}
+
+#[test]
+fn test_offset_map() {
+    let mut map = OffsetMap::new();
+    map.insert(10..20, 100).unwrap();
+    map.insert(15..=16, 15).unwrap();
+    assert_eq!(
+        (8..22).map(|v| map.get(v)).collect::<Vec<_>>(),
+        [8, 9, 100, 101, 102, 103, 104, 15, 16, 107, 108, 109, 20, 21]
+    );
+    assert_eq!(map.map_range(12..=17), [102..=104, 15..=16, 107..=107]);
+    assert_eq!(map.map_range(20..20), []);
+    assert!(map.insert(u64::MAX - 1.., u64::MAX).is_err());
+
+    let mut swap = OffsetMap::new();
+    swap.insert(0..5, 5).unwrap();
+    swap.insert(5..10, 0).unwrap();
+    assert_eq!(swap.then(&swap), OffsetMap::new());
+    assert_eq!(swap.inverse(), Some(swap.clone()));
+    assert_eq!(map.inverse(), None);
+
+    let both = map.then(&swap);
+    for v in 0..200 {
+        assert_eq!(both.get(v), swap.get(map.get(v)));
+    }
+    let mut top = OffsetMap::new();
+    top.insert(u64::MAX - 9.., 0).unwrap();
+    top.insert(..10, u64::MAX - 9).unwrap();
+    assert_eq!(top.get(u64::MAX), 9);
+    assert_eq!(top.inverse().unwrap().get(9), u64::MAX);
+    assert_eq!(
+        top.map_set(&IntervalSet::from_range(..)),
+        IntervalSet::from_range(..)
+    );
+}
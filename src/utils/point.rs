@@ -0,0 +1,465 @@
+//! Points and directions on a grid or in space.
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use eyre::Report;
+
+/// A point or vector in two dimensions.
+///
+/// `y` grows downwards, so [`Dir4::Up`] is `y - 1`, like rows in a
+/// [`Grid`](super::Grid).
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::point::{Dir4, Point2};
+///
+/// let p = Point2::new(0usize, 2);
+/// assert_eq!(p.checked_step(Dir4::Up), Some(Point2::new(0, 1)));
+/// assert_eq!(p.checked_step(Dir4::Left), None);
+/// assert_eq!(p.manhattan(Point2::new(3, 0)), 5);
+/// assert_eq!(Point2::new(1, 2) + Point2::new(3, 4), Point2::new(4, 6));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point or vector in three dimensions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(p: Point3<T>) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Component-wise operators
+macro_rules! impl_ops {
+    ($point:ident { $($c:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($c: self.$c + rhs.$c),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($c: self.$c - rhs.$c),* }
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($c: self.$c * rhs),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($c: -self.$c),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$c += rhs.$c;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$c -= rhs.$c;)*
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+/// Methods for a primitive integer `$t`, `$u` being its unsigned counterpart
+/// and `$checked_i8`/`$wrapping_i8` adding an `i8` to it
+macro_rules! impl_int {
+    ($($t:ty, $u:ty, $checked_i8:ident, $wrapping_i8:ident;)*) => {$(
+        impl Point2<$t> {
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                Some(Self::new(self.x.checked_add(rhs.x)?, self.y.checked_add(rhs.y)?))
+            }
+
+            pub fn wrapping_add(self, rhs: Self) -> Self {
+                Self::new(self.x.wrapping_add(rhs.x), self.y.wrapping_add(rhs.y))
+            }
+
+            /// One step in `dir`, `None` on overflow
+            pub fn checked_step(self, dir: impl Direction) -> Option<Self> {
+                let (dx, dy) = dir.delta();
+                Some(Self::new($checked_i8(self.x, dx)?, $checked_i8(self.y, dy)?))
+            }
+
+            /// One step in `dir`, wrapping around on overflow
+            pub fn wrapping_step(self, dir: impl Direction) -> Self {
+                let (dx, dy) = dir.delta();
+                Self::new($wrapping_i8(self.x, dx), $wrapping_i8(self.y, dy))
+            }
+
+            /// Taxicab distance
+            pub fn manhattan(self, other: Self) -> $u {
+                self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+            }
+
+            /// Chessboard distance, the number of king moves between the points
+            pub fn chebyshev(self, other: Self) -> $u {
+                self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+            }
+        }
+
+        impl Point3<$t> {
+            pub fn checked_add(self, rhs: Self) -> Option<Self> {
+                Some(Self::new(
+                    self.x.checked_add(rhs.x)?,
+                    self.y.checked_add(rhs.y)?,
+                    self.z.checked_add(rhs.z)?,
+                ))
+            }
+
+            pub fn wrapping_add(self, rhs: Self) -> Self {
+                Self::new(
+                    self.x.wrapping_add(rhs.x),
+                    self.y.wrapping_add(rhs.y),
+                    self.z.wrapping_add(rhs.z),
+                )
+            }
+
+            /// Taxicab distance
+            pub fn manhattan(self, other: Self) -> $u {
+                self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+            }
+
+            /// Chessboard distance, the number of king moves between the points
+            pub fn chebyshev(self, other: Self) -> $u {
+                self.x
+                    .abs_diff(other.x)
+                    .max(self.y.abs_diff(other.y))
+                    .max(self.z.abs_diff(other.z))
+            }
+        }
+    )*};
+}
+
+macro_rules! add_i8 {
+    ($($t:ty, $checked:ident, $wrapping:ident, $s:ty, $via:ident, $wrapping_via:ident;)*) => {$(
+        fn $checked(n: $t, d: i8) -> Option<$t> {
+            n.$via(d as $s)
+        }
+
+        fn $wrapping(n: $t, d: i8) -> $t {
+            n.$wrapping_via(d as $s)
+        }
+    )*};
+}
+
+add_i8! {
+    u8, checked_u8, wrapping_u8, i8, checked_add_signed, wrapping_add_signed;
+    u16, checked_u16, wrapping_u16, i16, checked_add_signed, wrapping_add_signed;
+    u32, checked_u32, wrapping_u32, i32, checked_add_signed, wrapping_add_signed;
+    u64, checked_u64, wrapping_u64, i64, checked_add_signed, wrapping_add_signed;
+    usize, checked_usize, wrapping_usize, isize, checked_add_signed, wrapping_add_signed;
+    i8, checked_i8, wrapping_i8, i8, checked_add, wrapping_add;
+    i16, checked_i16, wrapping_i16, i16, checked_add, wrapping_add;
+    i32, checked_i32, wrapping_i32, i32, checked_add, wrapping_add;
+    i64, checked_i64, wrapping_i64, i64, checked_add, wrapping_add;
+    isize, checked_isize, wrapping_isize, isize, checked_add, wrapping_add;
+}
+
+impl_int! {
+    u8, u8, checked_u8, wrapping_u8;
+    u16, u16, checked_u16, wrapping_u16;
+    u32, u32, checked_u32, wrapping_u32;
+    u64, u64, checked_u64, wrapping_u64;
+    usize, usize, checked_usize, wrapping_usize;
+    i8, u8, checked_i8, wrapping_i8;
+    i16, u16, checked_i16, wrapping_i16;
+    i32, u32, checked_i32, wrapping_i32;
+    i64, u64, checked_i64, wrapping_i64;
+    isize, usize, checked_isize, wrapping_isize;
+}
+
+/// A direction to step in, see [`Point2::checked_step`]
+pub trait Direction: Copy {
+    /// The `(dx, dy)` of one step, with `y` growing downwards
+    fn delta(self) -> (i8, i8);
+}
+
+/// The four orthogonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// The four orthogonal and four diagonal directions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir4 {
+    /// All directions, clockwise from [`Up`](Self::Up)
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Turn 90° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turn 90° counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Dir8 {
+    /// All directions, clockwise from [`Up`](Self::Up)
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turn 45° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turn 45° counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl Direction for Dir4 {
+    fn delta(self) -> (i8, i8) {
+        Dir8::from(self).delta()
+    }
+}
+
+impl Direction for Dir8 {
+    fn delta(self) -> (i8, i8) {
+        match self {
+            Dir8::Up => (0, -1),
+            Dir8::UpRight => (1, -1),
+            Dir8::Right => (1, 0),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (0, 1),
+            Dir8::DownLeft => (-1, 1),
+            Dir8::Left => (-1, 0),
+            Dir8::UpLeft => (-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = Report;
+
+    /// Parse `U/D/L/R`, `N/E/S/W` or `^v<>`
+    fn try_from(c: char) -> Result<Self, Report> {
+        match c {
+            'U' | 'N' | '^' => Ok(Self::Up),
+            'R' | 'E' | '>' => Ok(Self::Right),
+            'D' | 'S' | 'v' => Ok(Self::Down),
+            'L' | 'W' | '<' => Ok(Self::Left),
+            _ => eyre::bail!("invalid direction {c:?}"),
+        }
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self, Report> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => eyre::bail!("invalid direction {s:?}"),
+        }
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = Report;
+
+    /// Parse one or two of `U/D/L/R`, `N/E/S/W` or `^v<>`, e.g. `NE` or `DL`
+    fn from_str(s: &str) -> Result<Self, Report> {
+        let mut chars = s.trim().chars().map(Dir4::try_from);
+        let dir = match (chars.next(), chars.next(), chars.next()) {
+            (Some(dir), None, None) => Self::from(dir?),
+            (Some(a), Some(b), None) => {
+                let (a, b) = (Self::from(a?), Self::from(b?));
+                // the diagonal is between two orthogonal directions
+                Self::ALL
+                    .into_iter()
+                    .find(|d| {
+                        let (l, r) = (d.turn_left(), d.turn_right());
+                        (l, r) == (a, b) || (r, l) == (a, b)
+                    })
+                    .ok_or_else(|| eyre::eyre!("invalid direction {s:?}"))?
+            }
+            _ => eyre::bail!("invalid direction {s:?}"),
+        };
+        Ok(dir)
+    }
+}
+
+/// An index into a two dimensional array like `Vec<Vec<T>>`, see
+/// [`DisjointMut`](super::DisjointMut)
+pub trait GridIndex: Copy {
+    /// The outer and inner index
+    fn outer_inner(self) -> (usize, usize);
+}
+
+impl GridIndex for (usize, usize) {
+    fn outer_inner(self) -> (usize, usize) {
+        self
+    }
+}
+
+/// A point indexes the row `y`, then the column `x`
+impl GridIndex for Point2<usize> {
+    fn outer_inner(self) -> (usize, usize) {
+        (self.y, self.x)
+    }
+}
+
+#[test]
+fn test_points() {
+    let p = Point2::new(1i32, -2);
+    assert_eq!(p * 2 - Point2::new(1, 1), Point2::new(1, -5));
+    assert_eq!(-p, Point2::new(-1, 2));
+    assert_eq!(p.manhattan(Point2::new(-1, 1)), 5u32);
+    assert_eq!(p.chebyshev(Point2::new(-1, 1)), 3u32);
+    assert_eq!(p.checked_step(Dir8::DownLeft), Some(Point2::new(0, -1)));
+    assert_eq!(
+        Point2::new(i32::MAX, 0).checked_add(Point2::new(1, 0)),
+        None
+    );
+    assert_eq!(
+        Point2::new(0u8, 255).wrapping_step(Dir8::DownLeft),
+        Point2::new(255, 0)
+    );
+
+    let mut q = Point3::new(1u64, 2, 3);
+    q += Point3::new(1, 1, 1);
+    assert_eq!(q, Point3::from((2, 3, 4)));
+    assert_eq!(q.manhattan(Point3::default()), 9);
+    assert_eq!(q.chebyshev(Point3::default()), 4);
+    assert_eq!(q.checked_add(Point3::new(u64::MAX, 0, 0)), None);
+    assert_eq!(q.to_string(), "(2, 3, 4)");
+}
+
+#[test]
+fn test_directions() {
+    assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+    assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+    assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+    assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+    assert_eq!(Dir8::UpRight.reverse(), Dir8::DownLeft);
+
+    let parsed: Vec<Dir4> = "U>SL".chars().map(|c| c.try_into().unwrap()).collect();
+    assert_eq!(parsed, [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left]);
+    assert_eq!("v".parse::<Dir4>().unwrap(), Dir4::Down);
+    assert!("X".parse::<Dir4>().is_err());
+    assert!("UR".parse::<Dir4>().is_err());
+    assert_eq!("NE".parse::<Dir8>().unwrap(), Dir8::UpRight);
+    assert_eq!("LD".parse::<Dir8>().unwrap(), Dir8::DownLeft);
+    assert_eq!("W".parse::<Dir8>().unwrap(), Dir8::Left);
+    assert!("NS".parse::<Dir8>().is_err());
+}
@@ -0,0 +1,305 @@
+//! Statistics over sliding windows, updated in O(1) per step.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Add, Sub},
+};
+
+use super::IteratorExt;
+
+/// Counts how many of each value are in a window
+pub trait WindowCounter<T> {
+    /// Add a value, returns whether it wasn't in the window yet
+    fn add(&mut self, value: &T) -> bool;
+    /// Remove a value, returns whether it was the last one in the window
+    fn remove(&mut self, value: &T) -> bool;
+}
+
+/// Values with few enough possibilities to count them in an array
+pub trait SmallIndex: Copy {
+    /// Number of possible values
+    const ALPHABET: usize;
+
+    fn index(self) -> usize;
+}
+
+macro_rules! impl_small_index {
+    ($($t:ty => $u:ty),*) => {$(
+        impl SmallIndex for $t {
+            const ALPHABET: usize = <$u>::MAX as usize + 1;
+
+            fn index(self) -> usize {
+                self as $u as usize
+            }
+        }
+    )*};
+}
+
+impl_small_index!(u8 => u8, i8 => u8, u16 => u16, i16 => u16, bool => u8);
+
+/// [`WindowCounter`] with a slot for every possible value, the fast path for
+/// bytes
+#[derive(Debug, Clone)]
+pub struct ArrayCounter<T> {
+    counts: Vec<u32>,
+    _values: PhantomData<T>,
+}
+
+impl<T: SmallIndex> Default for ArrayCounter<T> {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; T::ALPHABET],
+            _values: PhantomData,
+        }
+    }
+}
+
+impl<T: SmallIndex> WindowCounter<T> for ArrayCounter<T> {
+    fn add(&mut self, value: &T) -> bool {
+        let count = &mut self.counts[value.index()];
+        *count += 1;
+        *count == 1
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        let count = &mut self.counts[value.index()];
+        *count -= 1;
+        *count == 0
+    }
+}
+
+/// [`WindowCounter`] for any hashable value
+#[derive(Debug, Clone)]
+pub struct HashCounter<T> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T> Default for HashCounter<T> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> WindowCounter<T> for HashCounter<T> {
+    fn add(&mut self, value: &T) -> bool {
+        let count = self.counts.entry(value.clone()).or_default();
+        *count += 1;
+        *count == 1
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        let count = self.counts.get_mut(value).expect("value is in the window");
+        *count -= 1;
+        if *count == 0 {
+            self.counts.remove(value);
+            return true;
+        }
+        false
+    }
+}
+
+/// Types with a default way of counting them in a window
+pub trait Distinct: Sized {
+    type Counter: WindowCounter<Self> + Default;
+}
+
+macro_rules! impl_distinct {
+    ($counter:ident: $($t:ty),*) => {$(
+        impl Distinct for $t {
+            type Counter = $counter<$t>;
+        }
+    )*};
+}
+
+impl_distinct!(ArrayCounter: u8, i8, u16, i16, bool);
+impl_distinct!(HashCounter: char, u32, u64, u128, usize, i32, i64, i128, isize, String);
+
+impl<'s> Distinct for &'s str {
+    type Counter = HashCounter<&'s str>;
+}
+
+/// Every window of `n` values in `s`, with how many distinct values it has.
+///
+/// # Examples
+///
+/// Get the position of the first distinct window of size 4.
+///
+/// ```rust
+/// use aoc::utils::rolling_distinct_windows;
+/// assert_eq!(rolling_distinct_windows(&vec![1,2,1,4,1,2,3], 4).position(|(_, c)| c == 4).unwrap(),3);
+/// ```
+pub fn rolling_distinct_windows<T: Distinct>(
+    s: &[T],
+    n: usize,
+) -> impl Iterator<Item = (&[T], usize)> {
+    rolling_distinct_windows_with(s, n, T::Counter::default())
+}
+
+/// [`rolling_distinct_windows`] counting with `counter`, for types without a
+/// [`Distinct`] impl
+pub fn rolling_distinct_windows_with<T, C: WindowCounter<T>>(
+    s: &[T],
+    n: usize,
+    mut counter: C,
+) -> impl Iterator<Item = (&[T], usize)> {
+    assert!(n > 0, "windows of 0 values");
+    let mut count = 0;
+    (0..s.len()).filter_map(move |i| {
+        if i >= n && counter.remove(&s[i - n]) {
+            count -= 1;
+        }
+        if counter.add(&s[i]) {
+            count += 1;
+        }
+        (i + 1 >= n).then(|| (&s[i + 1 - n..=i], count))
+    })
+}
+
+/// The best value of every window of `n`, by a monotonic queue of indices
+fn rolling_extreme<T>(
+    s: &[T],
+    n: usize,
+    better: impl Fn(&T, &T) -> bool,
+) -> impl Iterator<Item = &T> {
+    assert!(n > 0, "windows of 0 values");
+    let mut queue = VecDeque::with_capacity(n);
+    (0..s.len()).filter_map(move |i| {
+        while queue.back().is_some_and(|&j| !better(&s[j], &s[i])) {
+            queue.pop_back();
+        }
+        queue.push_back(i);
+        if queue.front().is_some_and(|&j| j + n <= i) {
+            queue.pop_front();
+        }
+        (i + 1 >= n).then(|| &s[queue[0]])
+    })
+}
+
+/// Sliding window statistics over slices
+///
+/// # Examples
+///
+/// ```rust
+/// use aoc::utils::RollingWindows;
+///
+/// let signal = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+/// let start = signal.rolling_distinct_counts(4).position(|c| c == 4);
+/// assert_eq!(start.map(|p| p + 4), Some(7));
+///
+/// let depths = [199, 200, 208, 210, 200, 207];
+/// assert_eq!(depths.rolling_min(3).collect::<Vec<_>>(), [&199, &200, &200, &200]);
+/// assert_eq!(depths.rolling_max(3).collect::<Vec<_>>(), [&208, &210, &210, &210]);
+/// assert_eq!(depths.rolling_sums(3).collect::<Vec<_>>(), [607, 618, 618, 617]);
+/// ```
+pub trait RollingWindows<T> {
+    /// Every window of `n` values with how many distinct values it has
+    fn rolling_distinct_windows<'a>(&'a self, n: usize) -> impl Iterator<Item = (&'a [T], usize)>
+    where
+        T: Distinct + 'a;
+
+    /// How many distinct values each window of `n` has
+    fn rolling_distinct_counts<'a>(&'a self, n: usize) -> impl Iterator<Item = usize>
+    where
+        T: Distinct + 'a, {
+        self.rolling_distinct_windows(n).map(|(_, count)| count)
+    }
+
+    /// Smallest value of each window of `n`, the last one on ties
+    fn rolling_min<'a>(&'a self, n: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: Ord + 'a;
+
+    /// Largest value of each window of `n`, the last one on ties
+    fn rolling_max<'a>(&'a self, n: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: Ord + 'a;
+
+    /// Sum of each window of `n`
+    fn rolling_sums(&self, n: usize) -> impl Iterator<Item = T>
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T>;
+}
+
+impl<T> RollingWindows<T> for [T] {
+    fn rolling_distinct_windows<'a>(&'a self, n: usize) -> impl Iterator<Item = (&'a [T], usize)>
+    where
+        T: Distinct + 'a, {
+        rolling_distinct_windows(self, n)
+    }
+
+    fn rolling_min<'a>(&'a self, n: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: Ord + 'a, {
+        rolling_extreme(self, n, |kept, new| kept < new)
+    }
+
+    fn rolling_max<'a>(&'a self, n: usize) -> impl Iterator<Item = &'a T>
+    where
+        T: Ord + 'a, {
+        rolling_extreme(self, n, |kept, new| kept > new)
+    }
+
+    fn rolling_sums(&self, n: usize) -> impl Iterator<Item = T>
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T>, {
+        self.iter().copied().sum_windows(n)
+    }
+}
+
+#[test]
+fn test_rolling_windows() {
+    let bytes = b"abcabbd";
+    let naive: Vec<usize> = bytes
+        .windows(3)
+        .map(|w| w.iter().collect::<std::collections::HashSet<_>>().len())
+        .collect();
+    assert_eq!(bytes.rolling_distinct_counts(3).collect::<Vec<_>>(), naive);
+    let chars: Vec<char> = "abcabbd".chars().collect();
+    assert_eq!(chars.rolling_distinct_counts(3).collect::<Vec<_>>(), naive);
+    let words = ["a", "b", "c", "a", "b", "b", "d"];
+    assert_eq!(
+        rolling_distinct_windows(&words, 3)
+            .map(|(w, c)| (w.join(""), c))
+            .last(),
+        Some(("bbd".to_owned(), 2))
+    );
+    let counted = rolling_distinct_windows_with(&[1.0f64, 2.0, 1.0], 2, FloatBits::default());
+    assert_eq!(counted.map(|(_, c)| c).collect::<Vec<_>>(), [2, 2]);
+    assert_eq!(bytes.rolling_distinct_counts(8).count(), 0);
+
+    let numbers = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+    for n in 1..=numbers.len() {
+        let mins: Vec<_> = numbers
+            .windows(n)
+            .map(|w| w.iter().min().unwrap())
+            .collect();
+        let maxs: Vec<_> = numbers
+            .windows(n)
+            .map(|w| w.iter().max().unwrap())
+            .collect();
+        let sums: Vec<i32> = numbers.windows(n).map(|w| w.iter().sum()).collect();
+        assert_eq!(numbers.rolling_min(n).collect::<Vec<_>>(), mins);
+        assert_eq!(numbers.rolling_max(n).collect::<Vec<_>>(), maxs);
+        assert_eq!(numbers.rolling_sums(n).collect::<Vec<_>>(), sums);
+    }
+}
+
+/// Counts floats by their bits, to test custom counters
+#[cfg(test)]
+#[derive(Default)]
+struct FloatBits(HashCounter<u64>);
+
+#[cfg(test)]
+impl WindowCounter<f64> for FloatBits {
+    fn add(&mut self, value: &f64) -> bool {
+        self.0.add(&value.to_bits())
+    }
+
+    fn remove(&mut self, value: &f64) -> bool {
+        self.0.remove(&value.to_bits())
+    }
+}
@@ -1,39 +1,11 @@
 use aoc::parts::*;
-use aoc::Solver;
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, Criterion};
 use y{{year}}_day{{day}}_{{title_snake}}::Solution;
 
 pub fn criterion_benchmark(c: &mut Criterion) {
     let input = aoc::Aoc::new(&{{year}}u32, &{{day}}u32).unwrap().input;
-    c.bench_with_input(
-        criterion::BenchmarkId::new("solve {year}} day {{day}} part1", "using given input"),
-        &input,
-        |b, input| {
-            b.iter(|| {
-                let inp =
-                    <Solution as Solver<Year{{year}}, Day{{day}}, Part1>>::generate_input(black_box(input))
-                        .unwrap();
-                let sol =
-                    <Solution as Solver<Year{{year}}, Day{{day}}, Part1>>::solve(black_box(&inp)).unwrap();
-                black_box(sol)
-            })
-        },
-    );
-
-    c.bench_with_input(
-        criterion::BenchmarkId::new("solve {year}} day {{day}} part2", "using given input"),
-        &input,
-        |b, input| {
-            b.iter(|| {
-                let inp =
-                    <Solution as Solver<Year{{year}}, Day{{day}}, Part2>>::generate_input(black_box(input))
-                        .unwrap();
-                let sol =
-                    <Solution as Solver<Year{{year}}, Day{{day}}, Part2>>::solve(black_box(&inp)).unwrap();
-                black_box(sol)
-            })
-        },
-    );
+    aoc::bench::bench_part::<Solution, Year{{year}}, Day{{day}}, Part1>(c, &input);
+    aoc::bench::bench_part::<Solution, Year{{year}}, Day{{day}}, Part2>(c, &input);
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -44,13 +44,18 @@ pub struct Solution {}
 
 impl Solution {}
 
+aoc::register!(Solution, Year{{year}}, Day{{day}}, Part1);
+aoc::register!(Solution, Year{{year}}, Day{{day}}, Part2);
+
 #[test]
 fn test_solution() -> Result<(), Report> {
     aoc::test_util::init();
     let input = r#"
-0
-    "#.trim();
-    assert_eq!(aoc::solve_with_input::<Solution, Year{{year}}, Day{{day}}, Part1>(input)?, 0);
+{{example}}
+"#;
+    // only the newlines around the example are dropped, leading spaces matter
+    let input = &input[1..input.len() - 1];
+    assert_eq!(aoc::solve_with_input::<Solution, Year{{year}}, Day{{day}}, Part1>(input)?, {{answer}});
     Ok(())
 }
 
@@ -58,9 +63,11 @@ fn test_solution() -> Result<(), Report> {
 fn test_solution_second() -> Result<(), Report> {
     aoc::test_util::init();
     let input = r#"
-0
-    "#.trim();
-    assert_eq!(aoc::solve_with_input::<Solution, Year{{year}}, Day{{day}}, Part2>(input)?, 0);
+{{example_second}}
+"#;
+    // only the newlines around the example are dropped, leading spaces matter
+    let input = &input[1..input.len() - 1];
+    assert_eq!(aoc::solve_with_input::<Solution, Year{{year}}, Day{{day}}, Part2>(input)?, {{answer_second}});
     Ok(())
 }
 
@@ -0,0 +1,363 @@
+//! Client for adventofcode.com
+//!
+//! Fetches puzzles and inputs and stores them in the same cache as `aocf`
+//! did, so [`aoc::Aoc`] can read them.
+
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc::{Aoc, Level};
+use eyre::{Result, WrapErr};
+
+use crate::html;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Sent with every request, as asked for by the puzzle author
+pub const USER_AGENT: &str = concat!(
+    "github.com/Emilgardis/advent_of_code xtask/",
+    env!("CARGO_PKG_VERSION")
+);
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    /// Minimum time between two requests
+    throttle: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            session: session.into().trim().to_owned(),
+            throttle: Duration::from_secs(2),
+            last_request: Cell::new(None),
+        }
+    }
+
+    /// Create a client from the environment.
+    ///
+    /// The session cookie is read from `$AOC_SESSION` or `.aocf/cookie` in
+    /// `root`, the base url from `$AOC_BASE_URL`.
+    pub fn from_env(root: &Path) -> Result<Self> {
+        let session = match std::env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => {
+                let path = root.join(".aocf/cookie");
+                std::fs::read_to_string(&path).wrap_err_with(|| {
+                    format!(
+                        "no session cookie found in $AOC_SESSION or {}",
+                        path.display()
+                    )
+                })?
+            }
+        };
+        let base_url =
+            std::env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        Ok(Self::new(base_url, session))
+    }
+
+    #[cfg(test)]
+    pub fn with_throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// Wait until we are allowed to send another request
+    fn throttle(&self) {
+        if let Some(last) = self.last_request.get() {
+            let elapsed = last.elapsed();
+            if elapsed < self.throttle {
+                std::thread::sleep(self.throttle - elapsed);
+            }
+        }
+        self.last_request.set(Some(Instant::now()));
+    }
+
+    pub(crate) fn request(&self, method: &str, path: &str) -> ureq::Request {
+        self.throttle();
+        self.agent
+            .request(method, &format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    fn get(&self, path: &str) -> Result<String> {
+        match self.request("GET", path).call() {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                eyre::bail!("GET {path} failed with {status}: {}", body.trim())
+            }
+            Err(e) => Err(e).wrap_err_with(|| format!("GET {path} failed")),
+        }
+    }
+
+    /// Fetch the html of the puzzle page
+    pub fn puzzle_page(&self, year: u32, day: u32) -> Result<String> {
+        self.get(&format!("/{year}/day/{day}"))
+    }
+
+    /// Fetch the puzzle input
+    pub fn input(&self, year: u32, day: u32) -> Result<String> {
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+
+    /// Fetch the puzzle and its input and store them in the cache in `root`.
+    ///
+    /// Answers already stored in the cache are kept.
+    pub fn fetch(&self, root: &Path, year: u32, day: u32) -> Result<Aoc> {
+        let page = self.puzzle_page(year, day)?;
+        let puzzle = Puzzle::parse(&page)?;
+        let existing = Aoc::on_root_dir(root, &year, &day).ok();
+        let input = match existing.as_ref().filter(|aoc| !aoc.input.is_empty()) {
+            Some(aoc) => aoc.input.clone(),
+            None => self.input(year, day)?,
+        };
+
+        let mut solution = existing.map(|aoc| aoc.solution).unwrap_or_default();
+        solution.extend(puzzle.solution);
+        let stars = solution.len() as u8;
+        let aoc = Aoc {
+            year,
+            day,
+            level: if stars == 0 {
+                Level::First
+            } else {
+                Level::Second
+            },
+            title: puzzle.title,
+            stars: Some(stars),
+            solution,
+            input,
+            brief: puzzle.brief,
+        };
+        aoc.save_on_root_dir(root)?;
+        Ok(aoc)
+    }
+}
+
+/// The interesting parts of a puzzle page
+#[derive(Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    pub title: String,
+    pub brief: BTreeMap<Level, String>,
+    pub solution: BTreeMap<Level, String>,
+}
+
+impl Puzzle {
+    pub fn parse(page: &str) -> Result<Self> {
+        let articles: Vec<_> = html::elements(page, r#"<article class="day-desc">"#).collect();
+        if articles.is_empty() {
+            eyre::bail!("no puzzle description found on page");
+        }
+        let levels = [Level::First, Level::Second];
+
+        let title = html::elements(articles[0], "<h2")
+            .next()
+            .map(|h2| h2.split_once('>').map_or(h2, |(_, title)| title))
+            .map(html::decode_entities)
+            .ok_or_else(|| eyre::eyre!("no title found"))?;
+        let title = title
+            .trim()
+            .trim_matches('-')
+            .trim()
+            .split_once(": ")
+            .map_or(title.as_str(), |(_, title)| title)
+            .trim_end_matches('-')
+            .trim()
+            .to_owned();
+
+        let brief = levels
+            .into_iter()
+            .zip(&articles)
+            .map(|(level, article)| (level, html::to_markdown(article)))
+            .collect();
+
+        let solution = levels
+            .into_iter()
+            .zip(page.split("Your puzzle answer was <code>").skip(1))
+            .filter_map(|(level, rest)| {
+                rest.split_once("</code>")
+                    .map(|(answer, _)| (level, html::decode_entities(answer)))
+            })
+            .collect();
+
+        Ok(Self {
+            title,
+            brief,
+            solution,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    //! A tiny http server that answers with canned responses
+
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    /// Start a server on a random port, returns its base url and a log of
+    /// all requests.
+    pub fn serve(
+        respond: impl Fn(&Request) -> (u16, String) + Send + 'static,
+    ) -> (String, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let log = Arc::new(Mutex::new(vec![]));
+        let requests = log.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_owned();
+                let path = parts.next().unwrap_or_default().to_owned();
+                let mut headers = vec![];
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let Some((name, value)) = line.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    headers.push((name.to_owned(), value.to_owned()));
+                }
+                let mut request = Request {
+                    method,
+                    path,
+                    headers,
+                    body: String::new(),
+                };
+                let len = request
+                    .header("content-length")
+                    .and_then(|l| l.parse().ok())
+                    .unwrap_or(0);
+                let mut body = vec![0; len];
+                reader.read_exact(&mut body).unwrap();
+                request.body = String::from_utf8(body).unwrap();
+
+                let (status, body) = respond(&request);
+                requests.lock().unwrap().push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        (url, log)
+    }
+
+    pub fn temp_root(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("xtask-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join(".aocf")).unwrap();
+        root
+    }
+}
+
+#[cfg(test)]
+const TEST_PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Report &amp; Repair ---</h2><p>Find the two entries that sum to <code>2020</code>.</p>
+<pre><code>1721
+299
+</code></pre>
+<p>Multiplying them together produces <code>1721 * 299 = <em>514579</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now three entries.</p>
+</article>
+<p>Answer: <form method="post" action="1/answer"><input type="hidden" name="level" value="2"/></form></p>
+</main>"#;
+
+#[test]
+fn test_parse_puzzle() {
+    let puzzle = Puzzle::parse(TEST_PAGE).unwrap();
+    assert_eq!(puzzle.title, "Report & Repair");
+    assert_eq!(
+        puzzle.solution,
+        BTreeMap::from([(Level::First, "1234".to_owned())])
+    );
+    assert_eq!(
+        puzzle.brief[&Level::First],
+        "## --- Day 1: Report & Repair ---\n\nFind the two entries that sum to `2020`.\n\n```\n1721\n299\n```\n\nMultiplying them together produces `1721 * 299 = *514579*`."
+    );
+    assert_eq!(
+        puzzle.brief[&Level::Second],
+        "## --- Part Two ---\n\nNow three entries."
+    );
+}
+
+#[test]
+fn test_fetch() {
+    let (url, log) = mock::serve(|req| match req.path.as_str() {
+        "/2020/day/1" => (200, TEST_PAGE.to_owned()),
+        "/2020/day/1/input" => (200, "1721\n299\n".to_owned()),
+        _ => (404, "not found".to_owned()),
+    });
+    let root = mock::temp_root("fetch");
+    let client = Client::new(url, "secret\n").with_throttle(Duration::ZERO);
+
+    let aoc = client.fetch(&root, 2020, 1).unwrap();
+    let cached = Aoc::on_root_dir(&root, &2020, &1).unwrap();
+    assert_eq!(cached.title, "Report & Repair");
+    assert_eq!(cached.input, "1721\n299\n");
+    assert_eq!(cached.level, Level::Second);
+    assert_eq!(cached.stars, Some(1));
+    assert_eq!(cached.brief, aoc.brief);
+
+    let requests = log.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    for request in requests.iter() {
+        assert_eq!(request.header("cookie"), Some("session=secret"));
+        assert_eq!(request.header("user-agent"), Some(USER_AGENT));
+    }
+    drop(requests);
+
+    assert!(client.fetch(&root, 2020, 2).is_err());
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_throttle() {
+    let (url, _) = mock::serve(|_| (200, String::new()));
+    let client = Client::new(url, "").with_throttle(Duration::from_millis(200));
+    let start = Instant::now();
+    client.input(2020, 1).unwrap();
+    client.input(2020, 1).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
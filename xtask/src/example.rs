@@ -0,0 +1,216 @@
+//! Finding the example input and its expected answer in a puzzle brief
+
+/// An example from the puzzle description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    /// The highlighted answer for the example, if one was found
+    pub answer: Option<String>,
+}
+
+impl Example {
+    /// Find the example in the markdown `brief` of a level, as made by
+    /// [`html::to_markdown`](crate::html::to_markdown).
+    ///
+    /// The input is the first code block introduced by a paragraph mentioning
+    /// an example, or the first code block if there is no such paragraph. The
+    /// answer is the last emphasized inline code in the brief.
+    pub fn extract(brief: &str) -> Option<Self> {
+        let mut blocks = vec![];
+        let mut text = String::new();
+        let mut rest = brief;
+        while let Some(start) = rest.find("```\n") {
+            let before = &rest[..start];
+            let Some(end) = rest[start + 4..].find("```") else {
+                break;
+            };
+            let block = &rest[start + 4..start + 4 + end];
+            let intro = before.trim_end().rsplit("\n\n").next().unwrap_or_default();
+            blocks.push((intro.to_ascii_lowercase().contains("example"), block));
+            text.push_str(before);
+            rest = &rest[start + 4 + end + 3..];
+        }
+        text.push_str(rest);
+
+        let (_, input) = blocks
+            .iter()
+            .find(|(example, _)| *example)
+            .or_else(|| blocks.first())?;
+        Some(Self {
+            input: input.trim_end_matches('\n').to_owned(),
+            answer: answers(&text).last().copied().map(str::to_owned),
+        })
+    }
+
+    /// Like [`extract`](Self::extract), but for the part 2 `brief`, which
+    /// usually reuses the example of part 1.
+    pub fn extract_second(brief: &str, first: Option<&Example>) -> Option<Self> {
+        Self::extract(brief).or_else(|| {
+            Some(Self {
+                input: first?.input.clone(),
+                answer: answers(brief).last().copied().map(str::to_owned),
+            })
+        })
+    }
+
+    /// The example input, `0` if there is none
+    pub fn input_or_placeholder(example: Option<&Self>) -> &str {
+        example.map_or("0", |e| e.input.as_str())
+    }
+
+    /// The answer as a rust literal, `0` if unknown
+    pub fn answer_literal(&self) -> String {
+        match &self.answer {
+            Some(answer) if answer.parse::<i64>().is_ok() => answer.clone(),
+            Some(answer) => format!("{answer:?}"),
+            None => "0".to_owned(),
+        }
+    }
+}
+
+/// Fill in `example` in `test_solution_second` of a day's `source`.
+///
+/// Returns `None` if the test already has an expected answer.
+pub fn fill_second_test(source: &str, example: &Example) -> Option<String> {
+    let start = source.find("fn test_solution_second")?;
+    let (head, test) = source.split_at(start);
+    let end = test.find("\n}\n").map_or(test.len(), |end| end + 3);
+    let (test, tail) = test.split_at(end);
+
+    let answer_start = test.find("(input)?,")? + "(input)?,".len();
+    let answer_start =
+        answer_start + test[answer_start..].len() - test[answer_start..].trim_start().len();
+    let after_answer = test[answer_start..].strip_prefix('0')?;
+    if !after_answer.trim_start().starts_with(')') {
+        return None;
+    }
+    let input_start = test.find("r#\"\n")? + "r#\"\n".len();
+    // older days indent the closing quote and trim the example
+    let input_end = input_start
+        + ["\n\"#", "\n    \"#"]
+            .iter()
+            .find_map(|end| test[input_start..].find(end))?;
+
+    Some(format!(
+        "{head}{}{}{}{}{after_answer}{tail}",
+        &test[..input_start],
+        example.input,
+        &test[input_end..answer_start],
+        example.answer_literal(),
+    ))
+}
+
+/// All emphasized inline code in `text`, i.e. `` `*42*` ``, `` *`42`* `` or
+/// the emphasized part of `` `1 + 2 = *3*` ``
+fn answers(text: &str) -> Vec<&str> {
+    let mut answers = vec![];
+    let mut spans = text.split('`');
+    let mut before = spans.next().unwrap_or_default();
+    while let (Some(code), Some(after)) = (spans.next(), spans.next()) {
+        if before.ends_with('*') && after.starts_with('*') {
+            answers.push(code);
+        } else if let [_, answer, _] = code.rsplitn(3, '*').collect::<Vec<_>>()[..] {
+            if answer.trim() == answer {
+                answers.push(answer);
+            }
+        }
+        before = after;
+    }
+    answers.retain(|a| !a.trim().is_empty());
+    answers
+}
+
+#[test]
+fn test_extract() {
+    let brief = "## --- Day 1: Report Repair ---
+
+Find the two entries that sum to `2020`.
+
+For example, suppose your expense report contained the following:
+
+```
+1721
+979
+299
+```
+
+In this list, the two entries that sum to `2020` are `1721` and `299`. \
+Multiplying them together produces `1721 * 299 = *514579*`, so the correct answer is `*514579*`.";
+    assert_eq!(
+        Example::extract(brief),
+        Some(Example {
+            input: "1721\n979\n299".to_owned(),
+            answer: Some("514579".to_owned()),
+        })
+    );
+
+    let brief = "Some rules:
+
+```
+a -> b
+```
+
+Here is an example:
+
+```
+RL
+
+AAA = (BBB, CCC)
+```
+
+Reaching `ZZZ` takes *`2`* steps. The code is `*ABC*`.";
+    let example = Example::extract(brief).unwrap();
+    assert_eq!(example.input, "RL\n\nAAA = (BBB, CCC)");
+    assert_eq!(example.answer.as_deref(), Some("ABC"));
+    assert_eq!(example.answer_literal(), "\"ABC\"");
+
+    let example = Example::extract("Nothing to see here, `*1*`.");
+    assert_eq!(example, None);
+    let example = Example::extract("```\n1\n```\n\nNo answer.").unwrap();
+    assert_eq!(example.answer_literal(), "0");
+}
+
+#[test]
+fn test_fill_second_test() {
+    let source = r##"fn test_solution() -> Result<(), Report> {
+    let input = r#"
+1
+    "#
+    .trim();
+    assert_eq!(aoc::solve_with_input::<Solution, Year2020, Day1, Part1>(input)?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_solution_second() -> Result<(), Report> {
+    let input = r#"
+0
+"#;
+    let input = &input[1..input.len() - 1];
+    assert_eq!(
+        aoc::solve_with_input::<Solution, Year2020, Day1, Part2>(input)?,
+        0
+    );
+    Ok(())
+}
+"##;
+    let example = Example {
+        input: "    [D]\n1\n2".to_owned(),
+        answer: Some("3".to_owned()),
+    };
+    let filled = fill_second_test(source, &example).unwrap();
+    assert_eq!(
+        filled,
+        source
+            .replace("\n0\n\"#", "\n    [D]\n1\n2\n\"#")
+            .replace("?,\n        0\n", "?,\n        3\n")
+    );
+    assert_eq!(fill_second_test(&filled, &example), None);
+
+    let old = source.replace(
+        "\n0\n\"#;\n    let input = &input[1..input.len() - 1];",
+        "\n0\n    \"#\n    .trim();",
+    );
+    let filled = fill_second_test(&old, &example).unwrap();
+    assert!(filled.contains("\n    [D]\n1\n2\n    \"#\n    .trim();"));
+}
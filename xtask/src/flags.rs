@@ -2,7 +2,6 @@ xflags::xflags! {
     src "./src/flags.rs"
     cmd app
     {
-        repeated -v, --verbose
         cmd new-day {
             optional -y, --year year: String
             optional -d, --day day: String
@@ -12,6 +11,21 @@ xflags::xflags! {
             optional -y, --year year: String
             optional -d, --day day: String
         }
+        /// Fetch the puzzle and input into the cache, without generating anything.
+        cmd fetch {
+            optional -y, --year year: u32
+            optional -d, --day day: u32
+        }
+        /// Submit an answer for the current level, or the given one.
+        cmd submit {
+            required answer: String
+            optional -y, --year year: u32
+            optional -d, --day day: u32
+            optional -l, --level level: u32
+            /// When rate limited, wait and try again.
+            optional -w, --wait
+        }
+        cmd update-runner {}
     }
 }
 // generated start
@@ -19,7 +33,6 @@ xflags::xflags! {
 // Run `env UPDATE_XFLAGS=1 cargo build` to regenerate.
 #[derive(Debug)]
 pub struct App {
-    pub verbose: u32,
     pub subcommand: AppCmd,
 }
 
@@ -27,6 +40,9 @@ pub struct App {
 pub enum AppCmd {
     NewDay(NewDay),
     Second(Second),
+    Fetch(Fetch),
+    Submit(Submit),
+    UpdateRunner(UpdateRunner),
 }
 
 #[derive(Debug)]
@@ -42,6 +58,25 @@ pub struct Second {
     pub day: Option<String>,
 }
 
+#[derive(Debug)]
+pub struct Fetch {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+}
+
+#[derive(Debug)]
+pub struct Submit {
+    pub answer: String,
+
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub level: Option<u32>,
+    pub wait: bool,
+}
+
+#[derive(Debug)]
+pub struct UpdateRunner;
+
 impl App {
     #[allow(dead_code)]
     pub fn from_env_or_exit() -> Self {
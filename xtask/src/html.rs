@@ -0,0 +1,163 @@
+//! Just enough HTML handling for the puzzle pages on adventofcode.com
+
+/// Convert a fragment of puzzle html into markdown.
+///
+/// Only the handful of tags used in puzzle descriptions are handled, anything
+/// else is reduced to its text.
+///
+/// * `<pre><code>` blocks become fenced code blocks
+/// * `<code>` becomes `` `inline code` ``
+/// * `<em>` becomes `*emphasis*`, except in code blocks
+/// * `<li>` becomes a `* ` list item
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        push_text(&mut out, &rest[..start], in_pre);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match (name.as_str(), closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("li", false) => out.push_str("* "),
+            ("li", true) => out.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            // emphasis can't be shown in code blocks, and would end up in the example input
+            ("em", _) if !in_pre => out.push('*'),
+            ("br", _) => out.push('\n'),
+            _ => (),
+        }
+    }
+    push_text(&mut out, rest, in_pre);
+
+    out.trim().to_owned()
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    if !in_pre && text.trim().is_empty() && (out.is_empty() || out.ends_with('\n')) {
+        return;
+    }
+    out.push_str(&decode_entities(text));
+}
+
+/// Decode the html entities used on adventofcode.com
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_owned();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|&end| end < 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let decoded = match &rest[1..end] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            entity => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Iterate over the inner html of all elements starting with `open`, e.g.
+/// `<article class="day-desc">`
+pub fn elements<'a>(html: &'a str, open: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let name = open
+        .trim_start_matches('<')
+        .split(|c: char| c.is_whitespace() || c == '>')
+        .next()
+        .unwrap_or_default();
+    let close = format!("</{name}>");
+    html.match_indices(open).filter_map(move |(start, _)| {
+        let inner = &html[start + open.len()..];
+        inner.find(&close).map(|end| &inner[..end])
+    })
+}
+
+#[test]
+fn test_to_markdown() {
+    let html = r#"<h2>--- Day 8: Haunted Wasteland ---</h2><p>It seems like you're meant to use the <em>left/right</em> instructions.</p>
+<pre><code>RL
+
+<em>AAA</em> = (BBB, CCC)
+</code></pre>
+<p>Starting at <code>AAA</code>, you reach <code>ZZZ</code> in <code><em>2</em></code> steps &amp; stop.</p>
+<ul>
+<li>one</li>
+<li>two &lt;3</li>
+</ul>"#;
+    assert_eq!(
+        to_markdown(html),
+        r#"## --- Day 8: Haunted Wasteland ---
+
+It seems like you're meant to use the *left/right* instructions.
+
+```
+RL
+
+AAA = (BBB, CCC)
+```
+
+Starting at `AAA`, you reach `ZZZ` in `*2*` steps & stop.
+
+* one
+* two <3"#
+    );
+}
+
+#[test]
+fn test_elements() {
+    let html = r#"<main><article class="day-desc"><p>one</p></article><p>between</p><article class="day-desc"><p>two</p></article></main>"#;
+    assert_eq!(
+        elements(html, r#"<article class="day-desc">"#).collect::<Vec<_>>(),
+        vec!["<p>one</p>", "<p>two</p>"]
+    );
+}
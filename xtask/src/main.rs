@@ -2,7 +2,11 @@ use std::io::Write;
 
 use eyre::{Result, WrapErr};
 
+mod client;
+mod example;
 mod flags;
+mod html;
+mod submit;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
@@ -15,11 +19,55 @@ fn main() -> Result<()> {
         flags::AppCmd::Second(second) => {
             update(&second).context("could not update day")?;
         }
+        flags::AppCmd::Fetch(fetch) => {
+            self::fetch(&fetch).context("could not fetch day")?;
+        }
+        flags::AppCmd::Submit(submit) => {
+            self::submit(&submit).context("could not submit answer")?;
+        }
+        flags::AppCmd::UpdateRunner(_) => {
+            update_runner(&project_root()).context("could not update runner")?;
+        }
     };
 
     Ok(())
 }
 
+fn fetch(flags: &flags::Fetch) -> Result<()> {
+    let date = time::OffsetDateTime::now_utc();
+    let year = flags.year.unwrap_or(date.year() as u32);
+    let day = flags.day.unwrap_or(date.day() as u32);
+    let root_dir = aoc::aoc::find_root()?;
+    let data = client::Client::from_env(&root_dir)?.fetch(&root_dir, year, day)?;
+    println!("fetched {year} day {day}: {}", data.title);
+    Ok(())
+}
+
+fn submit(flags: &flags::Submit) -> Result<()> {
+    use submit::Submission;
+    let date = time::OffsetDateTime::now_utc();
+    let year = flags.year.unwrap_or(date.year() as u32);
+    let day = flags.day.unwrap_or(date.day() as u32);
+    let level = flags
+        .level
+        .map(|l| aoc::Level::from_part(l).ok_or_else(|| eyre::eyre!("invalid level {l}")))
+        .transpose()?;
+    let root_dir = aoc::aoc::find_root()?;
+    let client = client::Client::from_env(&root_dir)?;
+    loop {
+        let submission =
+            client.submit_and_record(&root_dir, year, day, level, flags.answer.trim())?;
+        println!("{year} day {day}: {submission}");
+        match submission {
+            Submission::Correct => return Ok(()),
+            Submission::RateLimited(Some(wait)) if flags.wait => {
+                std::thread::sleep(wait + std::time::Duration::from_secs(1))
+            }
+            _ => eyre::bail!("answer {:?} was not accepted", flags.answer),
+        }
+    }
+}
+
 fn update(flags: &flags::Second) -> Result<()> {
     use inflections::case::to_snake_case;
     let sh = xshell::Shell::new()?;
@@ -33,17 +81,9 @@ fn update(flags: &flags::Second) -> Result<()> {
         year = date.year().to_string();
         (day.as_str(), year.as_str())
     };
-    xshell::cmd!(sh, "aocf checkout --day {day} --year {year}").run()?;
-    xshell::cmd!(sh, "aocf fetch").run()?;
-    xshell::cmd!(sh, "aocf input").run()?;
-    xshell::cmd!(sh, "aocf input").run()?;
-    std::thread::sleep(std::time::Duration::from_secs(2));
-    xshell::cmd!(sh, "aocf input").run()?;
-
-    // import the data.
     let root_dir = aoc::aoc::find_root()?;
-
-    let data = aoc::Aoc::on_root_dir(&root_dir, &year, &day)?;
+    let data =
+        client::Client::from_env(&root_dir)?.fetch(&root_dir, year.parse()?, day.parse()?)?;
 
     let day_dir = root_dir.join(format!(
         "{year}/day{day:0>2}-{}",
@@ -81,6 +121,21 @@ fn update(flags: &flags::Second) -> Result<()> {
             string.push('\n');
         }
     }
+    let first = data
+        .brief
+        .get(&aoc::Level::First)
+        .and_then(|brief| example::Example::extract(brief));
+    let second = data
+        .brief
+        .get(&aoc::Level::Second)
+        .and_then(|brief| example::Example::extract_second(brief, first.as_ref()));
+    if let Some(filled) = second
+        .as_ref()
+        .and_then(|second| example::fill_second_test(&string, second))
+    {
+        string = filled;
+    }
+
     let mut file = std::fs::OpenOptions::new()
         .create(true)
         .truncate(true)
@@ -101,17 +156,23 @@ fn generate_day(flags: &flags::NewDay) -> Result<()> {
         flags.day.as_deref().unwrap_or(&cur_day),
         flags.year.as_deref().unwrap_or(&cur_year),
     );
-    xshell::cmd!(sh, "aocf checkout --day {day} --year {year}").run()?;
-    xshell::cmd!(sh, "aocf fetch").run()?;
-    xshell::cmd!(sh, "aocf input").run()?;
-    xshell::cmd!(sh, "aocf input").run()?;
-    std::thread::sleep(std::time::Duration::from_secs(2));
-    xshell::cmd!(sh, "aocf input").run()?;
-
-    // import the data.
     let root_dir = aoc::aoc::find_root()?;
-
-    let data = aoc::Aoc::on_root_dir(&root_dir, &year, &day)?;
+    let data =
+        client::Client::from_env(&root_dir)?.fetch(&root_dir, year.parse()?, day.parse()?)?;
+    let example = data
+        .brief
+        .get(&aoc::Level::First)
+        .and_then(|brief| example::Example::extract(brief));
+    let example_second = data
+        .brief
+        .get(&aoc::Level::Second)
+        .and_then(|brief| example::Example::extract_second(brief, example.as_ref()))
+        .or_else(|| {
+            example.as_ref().map(|e| example::Example {
+                answer: None,
+                ..e.clone()
+            })
+        });
     // get the template files
     let template_dir = root_dir.join("template/.");
 
@@ -143,6 +204,26 @@ fn generate_day(flags: &flags::NewDay) -> Result<()> {
         );
         let contents = contents.replace("{{title}}", &data.title);
         let contents = contents.replace("{{level}}", &data.level.to_string());
+        let contents = contents.replace(
+            "{{example}}",
+            example::Example::input_or_placeholder(example.as_ref()),
+        );
+        let contents = contents.replace(
+            "{{answer}}",
+            &example
+                .as_ref()
+                .map_or("0".to_owned(), |e| e.answer_literal()),
+        );
+        let contents = contents.replace(
+            "{{example_second}}",
+            example::Example::input_or_placeholder(example_second.as_ref()),
+        );
+        let contents = contents.replace(
+            "{{answer_second}}",
+            &example_second
+                .as_ref()
+                .map_or("0".to_owned(), |e| e.answer_literal()),
+        );
         let contents = contents.replace(
             "{{brief}}",
             &data
@@ -178,7 +259,67 @@ fn generate_day(flags: &flags::NewDay) -> Result<()> {
             .open(new_file)?
             .write_all(contents.as_bytes())?;
     }
+    update_runner(&root_dir).context("could not update runner")?;
     xshell::cmd!(sh, "cargo fmt").run()?;
 
     Ok(())
 }
+
+/// Make the `aoc` runner depend on and link every day crate, so that their
+/// solvers end up in the registry.
+fn update_runner(root_dir: &std::path::Path) -> Result<()> {
+    let mut days = vec![];
+    for dir_entry in walkdir::WalkDir::new(root_dir).min_depth(2).max_depth(2) {
+        let dir_entry = dir_entry?;
+        let path = dir_entry.path();
+        let Ok(relative) = path.strip_prefix(root_dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        if !relative.starts_with("20") || !relative.contains("/day") {
+            continue;
+        }
+        let Ok(manifest) = std::fs::read_to_string(path.join("Cargo.toml")) else {
+            continue;
+        };
+        let name = manifest
+            .lines()
+            .find_map(|l| l.strip_prefix("name = "))
+            .map(|n| n.trim().trim_matches('"').to_owned())
+            .ok_or_else(|| eyre::eyre!("no package name in {}", path.display()))?;
+        days.push((relative, name));
+    }
+    days.sort();
+
+    let manifest_path = root_dir.join("runner/Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("could not read {}", manifest_path.display()))?;
+    let (head, _) = manifest
+        .split_once("# generated start\n")
+        .ok_or_else(|| eyre::eyre!("no `# generated start` in runner manifest"))?;
+    let mut new_manifest = format!(
+        "{head}# generated start\n# The following dependencies are generated by `cargo xtask new-day`.\n"
+    );
+    for (path, name) in &days {
+        new_manifest.push_str(&format!("{name} = {{ path = \"../{path}\" }}\n"));
+    }
+    new_manifest.push_str("# generated end\n");
+    std::fs::write(&manifest_path, new_manifest)?;
+
+    let mut days_rs = String::from(
+        "//! Links every day crate into the runner.\n//!\n//! Generated by `cargo xtask new-day`, do not edit.\n\n",
+    );
+    for (_, name) in &days {
+        days_rs.push_str(&format!("use {} as _;\n", name.replace('-', "_")));
+    }
+    std::fs::write(root_dir.join("runner/src/days.rs"), days_rs)?;
+    Ok(())
+}
+
+fn project_root() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(1)
+        .unwrap()
+        .to_path_buf()
+}
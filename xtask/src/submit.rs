@@ -0,0 +1,238 @@
+//! Submitting answers to adventofcode.com
+
+use std::{fmt, path::Path, time::Duration};
+
+use aoc::{Aoc, Level};
+use eyre::{Result, WrapErr};
+
+use crate::{client::Client, html};
+
+/// The verdict on a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The level was already solved, or isn't unlocked yet
+    AlreadySolved,
+    /// An answer was submitted too recently, try again after the given time
+    RateLimited(Option<Duration>),
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Submission::Correct => f.write_str("that's the right answer"),
+            Submission::TooHigh => f.write_str("that's not the right answer, it's too high"),
+            Submission::TooLow => f.write_str("that's not the right answer, it's too low"),
+            Submission::Wrong => f.write_str("that's not the right answer"),
+            Submission::AlreadySolved => f.write_str("wrong level, did you already complete it?"),
+            Submission::RateLimited(Some(wait)) => {
+                write!(
+                    f,
+                    "answered too recently, wait {wait:?} before trying again"
+                )
+            }
+            Submission::RateLimited(None) => f.write_str("answered too recently"),
+        }
+    }
+}
+
+impl Submission {
+    /// Parse the page returned after posting an answer
+    pub fn parse(page: &str) -> Result<Self> {
+        let text = html::elements(page, "<article")
+            .next()
+            .map(html::to_markdown)
+            .unwrap_or_else(|| html::to_markdown(page));
+
+        if text.contains("That's the right answer") {
+            Ok(Submission::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Ok(Submission::TooHigh)
+            } else if text.contains("too low") {
+                Ok(Submission::TooLow)
+            } else {
+                Ok(Submission::Wrong)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Ok(Submission::RateLimited(parse_wait(&text)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Ok(Submission::AlreadySolved)
+        } else {
+            eyre::bail!("unknown response: {text}")
+        }
+    }
+}
+
+/// Parse the wait time in e.g. `You have 1m 5s left to wait`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+    let mut secs = 0;
+    for part in time.split_whitespace() {
+        let unit = part.trim_start_matches(|c: char| c.is_ascii_digit());
+        let value: u64 = part[..part.len() - unit.len()].parse().ok()?;
+        secs += value
+            * match unit {
+                "h" => 60 * 60,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+impl Client {
+    /// Post `answer` for `level`
+    pub fn submit(&self, year: u32, day: u32, level: Level, answer: &str) -> Result<Submission> {
+        let path = format!("/{year}/day/{day}/answer");
+        let level = level.part().to_string();
+        match self
+            .request("POST", &path)
+            .send_form(&[("level", &level), ("answer", answer)])
+        {
+            Ok(response) => Submission::parse(&response.into_string()?),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                eyre::bail!("POST {path} failed with {status}: {}", body.trim())
+            }
+            Err(e) => Err(e).wrap_err_with(|| format!("POST {path} failed")),
+        }
+    }
+
+    /// Submit `answer` for the current level of the cached puzzle in `root`,
+    /// and record it in the cache if it was correct.
+    pub fn submit_and_record(
+        &self,
+        root: &Path,
+        year: u32,
+        day: u32,
+        level: Option<Level>,
+        answer: &str,
+    ) -> Result<Submission> {
+        let mut aoc = Aoc::on_root_dir(root, &year, &day)?;
+        let level = level.unwrap_or(aoc.level);
+        let submission = self.submit(year, day, level, answer)?;
+        if submission == Submission::Correct {
+            aoc.solution.insert(level, answer.to_owned());
+            aoc.stars = Some(aoc.stars.unwrap_or(0).saturating_add(1).min(2));
+            aoc.level = Level::Second;
+            aoc.save_on_root_dir(root)?;
+        }
+        Ok(submission)
+    }
+}
+
+#[test]
+fn test_parse_submission() {
+    let article = |text: &str| format!("<main>\n<article><p>{text}</p></article>\n</main>");
+    assert_eq!(
+        Submission::parse(&article(
+            r#"That's the right answer!  You are <span class="day-success">one gold star</span> closer to restoring snow operations."#
+        ))
+        .unwrap(),
+        Submission::Correct
+    );
+    assert_eq!(
+        Submission::parse(&article(
+            "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again. <a href=\"/2023/day/1\">[Return to Day 1]</a>"
+        ))
+        .unwrap(),
+        Submission::TooHigh
+    );
+    assert_eq!(
+        Submission::parse(&article(
+            "That's not the right answer; your answer is too low."
+        ))
+        .unwrap(),
+        Submission::TooLow
+    );
+    assert_eq!(
+        Submission::parse(&article(
+            "That's not the right answer.  If you're stuck, make sure you're using the full input data."
+        ))
+        .unwrap(),
+        Submission::Wrong
+    );
+    assert_eq!(
+        Submission::parse(&article(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        ))
+        .unwrap(),
+        Submission::AlreadySolved
+    );
+    assert_eq!(
+        Submission::parse(&article(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+        ))
+        .unwrap(),
+        Submission::RateLimited(Some(Duration::from_secs(65)))
+    );
+    assert_eq!(
+        Submission::parse(&article(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 31s left to wait."
+        ))
+        .unwrap(),
+        Submission::RateLimited(Some(Duration::from_secs(31)))
+    );
+    assert!(Submission::parse(&article("Something else")).is_err());
+}
+
+#[test]
+fn test_submit() {
+    use crate::client::mock;
+
+    let (url, log) = mock::serve(|req| {
+        let text = if req.body.contains("answer=42") {
+            "That's the right answer!"
+        } else {
+            "That's not the right answer; your answer is too low."
+        };
+        (200, format!("<article><p>{text}</p></article>"))
+    });
+    let root = mock::temp_root("submit");
+    Aoc {
+        year: 2020,
+        day: 1,
+        level: Level::First,
+        title: "Report Repair".to_owned(),
+        stars: Some(0),
+        solution: Default::default(),
+        input: "1\n".to_owned(),
+        brief: Default::default(),
+    }
+    .save_on_root_dir(&root)
+    .unwrap();
+    let client = Client::new(url, "secret").with_throttle(Duration::ZERO);
+
+    assert_eq!(
+        client.submit_and_record(&root, 2020, 1, None, "1").unwrap(),
+        Submission::TooLow
+    );
+    assert!(Aoc::on_root_dir(&root, &2020, &1)
+        .unwrap()
+        .solution
+        .is_empty());
+
+    assert_eq!(
+        client
+            .submit_and_record(&root, 2020, 1, None, "42")
+            .unwrap(),
+        Submission::Correct
+    );
+    let aoc = Aoc::on_root_dir(&root, &2020, &1).unwrap();
+    assert_eq!(aoc.solution[&Level::First], "42");
+    assert_eq!(aoc.stars, Some(1));
+    assert_eq!(aoc.level, Level::Second);
+
+    let requests = log.lock().unwrap();
+    assert_eq!(requests[1].method, "POST");
+    assert_eq!(requests[1].path, "/2020/day/1/answer");
+    assert_eq!(requests[1].body, "level=1&answer=42");
+    drop(requests);
+    std::fs::remove_dir_all(&root).unwrap();
+}